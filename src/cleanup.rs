@@ -7,6 +7,7 @@ use bevy::{
 
 pub trait Cleanup {
     fn cleanup_resource<Res: Resource>(&mut self, on_exit: impl States) -> &mut Self;
+    fn reset_resource<Res: Resource + Default>(&mut self, on_exit: impl States) -> &mut Self;
 }
 
 impl Cleanup for App {
@@ -16,4 +17,11 @@ impl Cleanup for App {
         });
         self
     }
+
+    fn reset_resource<Res: Resource + Default>(&mut self, on_exit: impl States) -> &mut Self {
+        self.add_systems(OnExit(on_exit), |mut commands: Commands| {
+            commands.insert_resource(Res::default());
+        });
+        self
+    }
 }
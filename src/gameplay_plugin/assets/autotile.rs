@@ -0,0 +1,282 @@
+use bevy::{asset::Handle, math::UVec2, reflect::Reflect, sprite::TextureAtlasLayout};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::gameplay_plugin::save_file::{ConnectionTypePath, TileTypePath};
+
+/// The size of a frame of an autotile atlas, which is the size of a tile.
+pub const FRAME_SIZE: UVec2 = UVec2::new(32, 28);
+
+/// How a tile type chooses its sprite from its neighbours, so that e.g. roads and coastlines join up.
+/// The texture of the tile type is an atlas of frames of [FRAME_SIZE], read row by row.
+///
+/// The neighbours a tile joins with form a mask, where bit `i` stands for the neighbour `i` of [crate::gameplay_plugin::components::AxialCoordinates::neighbours()].
+/// The frame is chosen by the first rule matching the mask. Without a matching rule the mask itself is the index of the frame, so an atlas with all 64 combinations needs no rules.
+#[derive(Reflect, Debug, Clone, PartialEq)]
+pub struct Autotile {
+    layout: Handle<TextureAtlasLayout>,
+    frames: usize,
+    /// The tile types besides its own a tile joins with.
+    tile_types: Vec<TileTypePath>,
+    /// A tile joins with any neighbour it is connected to by one of these connection types.
+    connection_types: Vec<ConnectionTypePath>,
+    /// Whether a tile joins with the edge of the map, e.g. for roads leading out of the map.
+    map_edge: bool,
+    rules: Vec<AutotileRule>,
+}
+
+/// What lies in the direction of a neighbour of a tile.
+#[derive(Copy, Debug, Clone, PartialEq, Eq)]
+pub enum Neighbour<'a> {
+    MapEdge,
+    Tile {
+        tile_type: &'a TileTypePath,
+        /// The type of the connection between the tiles, if it has one.
+        connection_type: Option<&'a ConnectionTypePath>,
+    },
+}
+
+impl Autotile {
+    pub fn layout(&self) -> &Handle<TextureAtlasLayout> {
+        &self.layout
+    }
+
+    /// Whether a tile of the type `tile_type` joins with its `neighbour`.
+    pub fn joins(&self, tile_type: &TileTypePath, neighbour: Neighbour) -> bool {
+        match neighbour {
+            Neighbour::MapEdge => self.map_edge,
+            Neighbour::Tile {
+                tile_type: neighbour_tile_type,
+                connection_type,
+            } => {
+                neighbour_tile_type == tile_type
+                    || self.tile_types.contains(neighbour_tile_type)
+                    || connection_type.is_some_and(|connection_type| {
+                        self.connection_types.contains(connection_type)
+                    })
+            }
+        }
+    }
+
+    /// The frame of the atlas for a tile joining with the neighbours in `mask`.
+    /// Falls back to the first frame if the atlas has no frame for the mask.
+    pub fn index(&self, mask: u8) -> usize {
+        match self.rules.iter().find(|rule| rule.matches(mask)) {
+            Some(rule) => rule.index,
+            None if usize::from(mask) < self.frames => usize::from(mask),
+            None => 0,
+        }
+    }
+}
+
+/// The mask of the neighbours a tile joins with, starting with the right neighbour and going clockwise.
+pub fn neighbour_mask(joined: [bool; 6]) -> u8 {
+    joined
+        .into_iter()
+        .enumerate()
+        .filter(|(_, joined)| *joined)
+        .fold(0, |mask, (direction, _)| mask | 1 << direction)
+}
+
+#[derive(Reflect, Copy, Debug, Clone, PartialEq, Eq)]
+struct AutotileRule {
+    /// The neighbours that have to be joined, among the relevant ones.
+    joined: u8,
+    /// The neighbours the rule cares about.
+    relevant: u8,
+    index: usize,
+}
+
+impl AutotileRule {
+    /// Parse a pattern of six characters, one per neighbour starting with the right one and going clockwise:
+    /// `1` if the tile has to join with the neighbour, `0` if it must not and `*` if it doesn't matter.
+    fn parse(pattern: &str, index: usize) -> Result<Self, AutotileError> {
+        let invalid = || AutotileError::InvalidPattern {
+            pattern: pattern.to_string(),
+        };
+        if pattern.chars().count() != 6 {
+            return Err(invalid());
+        }
+        let mut rule = AutotileRule {
+            joined: 0,
+            relevant: 0,
+            index,
+        };
+        for (direction, character) in pattern.chars().enumerate() {
+            match character {
+                '1' => {
+                    rule.joined |= 1 << direction;
+                    rule.relevant |= 1 << direction;
+                }
+                '0' => rule.relevant |= 1 << direction,
+                '*' => {}
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(rule)
+    }
+
+    fn matches(&self, mask: u8) -> bool {
+        mask & self.relevant == self.joined
+    }
+}
+
+/// The `autotile` of a `tile_type.ron` file as it is written.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub(super) struct AutotileDefinition {
+    columns: u32,
+    rows: u32,
+    #[serde(default)]
+    tile_types: Vec<TileTypePath>,
+    #[serde(default)]
+    connection_types: Vec<ConnectionTypePath>,
+    #[serde(default)]
+    map_edge: bool,
+    #[serde(default)]
+    rules: Vec<AutotileRuleDefinition>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+struct AutotileRuleDefinition {
+    neighbours: String,
+    index: usize,
+}
+
+impl AutotileDefinition {
+    /// The layout of the atlas, which has to be added to the assets before creating the autotile.
+    pub(super) fn layout(&self) -> TextureAtlasLayout {
+        TextureAtlasLayout::from_grid(FRAME_SIZE, self.columns, self.rows, None, None)
+    }
+
+    pub(super) fn into_autotile(
+        self,
+        layout: Handle<TextureAtlasLayout>,
+    ) -> Result<Autotile, AutotileError> {
+        let frames = self.columns as usize * self.rows as usize;
+        if frames == 0 {
+            return Err(AutotileError::Empty);
+        }
+        let rules = self
+            .rules
+            .iter()
+            .map(|AutotileRuleDefinition { neighbours, index }| {
+                if *index >= frames {
+                    return Err(AutotileError::IndexOutOfBounds {
+                        index: *index,
+                        frames,
+                    });
+                }
+                AutotileRule::parse(neighbours, *index)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Autotile {
+            layout,
+            frames,
+            tile_types: self.tile_types,
+            connection_types: self.connection_types,
+            map_edge: self.map_edge,
+            rules,
+        })
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AutotileError {
+    #[error("The atlas has no frames")]
+    Empty,
+    #[error("The rule `{pattern}` must consist of six of the characters `1`, `0` and `*`")]
+    InvalidPattern { pattern: String },
+    #[error("A rule refers to the frame {index}, but the atlas only has {frames} frames")]
+    IndexOutOfBounds { index: usize, frames: usize },
+}
+
+#[cfg(test)]
+mod tests_autotile {
+    use bevy::asset::Handle;
+    use ron::de::from_str;
+
+    use super::{neighbour_mask, AutotileDefinition, AutotileError, Neighbour};
+
+    fn parse(source: &str) -> Result<super::Autotile, AutotileError> {
+        from_str::<AutotileDefinition>(source)
+            .unwrap()
+            .into_autotile(Handle::default())
+    }
+
+    #[test]
+    fn test_neighbour_mask() {
+        assert_eq!(neighbour_mask([false; 6]), 0);
+        assert_eq!(
+            neighbour_mask([true, false, false, true, false, false]),
+            0b001001
+        );
+        assert_eq!(neighbour_mask([true; 6]), 0b111111);
+    }
+
+    #[test]
+    fn test_joins() {
+        let autotile = parse(
+            "(columns: 8, rows: 8, tile_types: [\"urban\"], connection_types: [\"road\"], map_edge: true)",
+        )
+        .unwrap();
+        let road = "road".to_string();
+        let urban = "urban".to_string();
+        let forest = "forest".to_string();
+        let river = "river".to_string();
+        let tile = |tile_type, connection_type| Neighbour::Tile {
+            tile_type,
+            connection_type,
+        };
+        assert!(autotile.joins(&road, tile(&road, None)));
+        assert!(autotile.joins(&road, tile(&urban, None)));
+        assert!(!autotile.joins(&road, tile(&forest, None)));
+        assert!(autotile.joins(&road, tile(&forest, Some(&road))));
+        assert!(!autotile.joins(&road, tile(&forest, Some(&river))));
+        assert!(autotile.joins(&road, Neighbour::MapEdge));
+    }
+
+    #[test]
+    fn test_index() {
+        let autotile = parse(
+            "(columns: 3, rows: 1, rules: [(neighbours: \"111111\", index: 0), (neighbours: \"1*****\", index: 1), (neighbours: \"0*****\", index: 2)])",
+        )
+        .unwrap();
+        assert_eq!(autotile.index(0b111111), 0);
+        assert_eq!(autotile.index(0b000001), 1);
+        assert_eq!(autotile.index(0b011110), 2);
+
+        // Without rules the mask is the index.
+        let autotile = parse("(columns: 8, rows: 8)").unwrap();
+        assert_eq!(autotile.index(0b101010), 0b101010);
+        let autotile = parse("(columns: 4, rows: 1)").unwrap();
+        assert_eq!(autotile.index(3), 3);
+        assert_eq!(autotile.index(5), 0);
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        assert_eq!(
+            parse("(columns: 2, rows: 1, rules: [(neighbours: \"11\", index: 0)])"),
+            Err(AutotileError::InvalidPattern {
+                pattern: "11".to_string()
+            })
+        );
+        assert_eq!(
+            parse("(columns: 2, rows: 1, rules: [(neighbours: \"11x111\", index: 0)])"),
+            Err(AutotileError::InvalidPattern {
+                pattern: "11x111".to_string()
+            })
+        );
+        assert_eq!(
+            parse("(columns: 2, rows: 1, rules: [(neighbours: \"111111\", index: 2)])"),
+            Err(AutotileError::IndexOutOfBounds {
+                index: 2,
+                frames: 2
+            })
+        );
+        assert_eq!(parse("(columns: 0, rows: 1)"), Err(AutotileError::Empty));
+    }
+}
@@ -0,0 +1,220 @@
+use std::path::{Path, PathBuf};
+
+use bevy::{
+    asset::{io::Reader, Asset, AssetLoader, AsyncReadExt, Handle, LoadContext},
+    reflect::Reflect,
+    render::texture::Image,
+};
+use ron::de::from_bytes;
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::{diagnostics::RonDiagnostic, directory_name, present};
+
+fn default_texture() -> PathBuf {
+    PathBuf::from("texture.png")
+}
+
+/// What lies between two adjacent tiles, e.g. a river or a cliff.
+#[derive(Reflect, Asset, Debug, Clone, PartialEq)]
+pub struct ConnectionType {
+    /// Defaults to the name of the directory the connection type is defined in.
+    display_name: String,
+    /// Drawn on top of the border between the tiles.
+    /// The texture is drawn for the border to the right neighbour and rotated for the other borders.
+    texture: Handle<Image>,
+    /// Added to the movement cost of the tile entered across this connection.
+    /// Negative for connections like roads which make crossing cheaper.
+    movement_cost: i8,
+    blocks_movement: bool,
+    /// The share of damage an attack across this connection loses.
+    combat_penalty: f32,
+    /// Units exert no zone of control across this connection, e.g. a river.
+    negates_zone_of_control: bool,
+    /// Supply flows across this connection without losing any, e.g. a road.
+    carries_supply: bool,
+}
+
+impl ConnectionType {
+    pub fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    pub fn texture(&self) -> &Handle<Image> {
+        &self.texture
+    }
+
+    pub fn movement_cost(&self) -> i8 {
+        self.movement_cost
+    }
+
+    pub fn blocks_movement(&self) -> bool {
+        self.blocks_movement
+    }
+
+    pub fn combat_penalty(&self) -> f32 {
+        self.combat_penalty
+    }
+
+    pub fn negates_zone_of_control(&self) -> bool {
+        self.negates_zone_of_control
+    }
+
+    pub fn carries_supply(&self) -> bool {
+        self.carries_supply
+    }
+
+    /// The movement cost of entering a tile costing `tile_movement_cost` across this connection.
+    /// Crossing always costs at least one movement point.
+    ///
+    /// ## Returns
+    /// - None: The connection can not be crossed.
+    pub fn movement_cost_across(&self, tile_movement_cost: u8) -> Option<u8> {
+        if self.blocks_movement {
+            return None;
+        }
+        let movement_cost = i16::from(tile_movement_cost) + i16::from(self.movement_cost);
+        Some(movement_cost.clamp(1, i16::from(u8::MAX)) as u8)
+    }
+}
+
+/// A `connection_type.ron` file as it is written.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename = "ConnectionType", deny_unknown_fields)]
+struct ConnectionTypeDefinition {
+    #[serde(default, deserialize_with = "present")]
+    display_name: Option<String>,
+    /// Relative to the directory of the definition.
+    #[serde(default = "default_texture")]
+    texture: PathBuf,
+    #[serde(default)]
+    movement_cost: i8,
+    #[serde(default)]
+    blocks_movement: bool,
+    #[serde(default)]
+    combat_penalty: f32,
+    #[serde(default)]
+    negates_zone_of_control: bool,
+    #[serde(default)]
+    carries_supply: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionTypeLoader;
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum ConnectionTypeLoaderError {
+    /// An [IO](std::io) Error
+    #[error("Could not read data of the connection type at {}: {error}", path.display())]
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    /// A [RON](ron) Error
+    #[error("Invalid connection type: {0}")]
+    Ron(#[from] RonDiagnostic),
+}
+
+impl AssetLoader for ConnectionTypeLoader {
+    type Asset = ConnectionType;
+    type Settings = ();
+    type Error = ConnectionTypeLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(|error| ConnectionTypeLoaderError::Io {
+                path: load_context.path().to_path_buf(),
+                error,
+            })?;
+        let ConnectionTypeDefinition {
+            display_name,
+            texture,
+            movement_cost,
+            blocks_movement,
+            combat_penalty,
+            negates_zone_of_control,
+            carries_supply,
+        } = from_bytes::<ConnectionTypeDefinition>(&bytes)
+            .map_err(|error| RonDiagnostic::new(load_context.path(), &bytes, error))?;
+        let directory = load_context
+            .path()
+            .parent()
+            .unwrap_or(Path::new(""))
+            .to_path_buf();
+        Ok(ConnectionType {
+            display_name: display_name.unwrap_or_else(|| directory_name(load_context)),
+            texture: load_context.load(directory.join(texture)),
+            movement_cost,
+            blocks_movement,
+            combat_penalty,
+            negates_zone_of_control,
+            carries_supply,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["connection"]
+    }
+}
+
+#[cfg(test)]
+mod tests_connection_type {
+    use bevy::asset::Handle;
+    use ron::de::from_str;
+
+    use super::{ConnectionType, ConnectionTypeDefinition};
+
+    fn connection_type(movement_cost: i8, blocks_movement: bool) -> ConnectionType {
+        ConnectionType {
+            display_name: String::new(),
+            texture: Handle::default(),
+            movement_cost,
+            blocks_movement,
+            combat_penalty: 0.0,
+            negates_zone_of_control: false,
+            carries_supply: false,
+        }
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let definition = from_str::<ConnectionTypeDefinition>("ConnectionType ()").unwrap();
+        assert_eq!(definition.display_name, None);
+        assert_eq!(definition.texture.to_str(), Some("texture.png"));
+        assert_eq!(definition.movement_cost, 0);
+        assert!(!definition.blocks_movement);
+        assert_eq!(definition.combat_penalty, 0.0);
+        assert!(!definition.negates_zone_of_control);
+        assert!(!definition.carries_supply);
+    }
+
+    #[test]
+    fn test_display_name() {
+        let definition =
+            from_str::<ConnectionTypeDefinition>("ConnectionType (display_name: \"River\")")
+                .unwrap();
+        assert_eq!(definition.display_name.as_deref(), Some("River"));
+    }
+
+    #[test]
+    fn test_movement_cost_across() {
+        assert_eq!(connection_type(2, false).movement_cost_across(1), Some(3));
+        assert_eq!(connection_type(-1, false).movement_cost_across(3), Some(2));
+        // Crossing is never free.
+        assert_eq!(connection_type(-4, false).movement_cost_across(2), Some(1));
+        assert_eq!(
+            connection_type(4, false).movement_cost_across(u8::MAX),
+            Some(u8::MAX)
+        );
+        assert_eq!(connection_type(0, true).movement_cost_across(1), None);
+    }
+}
@@ -0,0 +1,226 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use ron::{error::SpannedError, Error as RonError};
+use thiserror::Error;
+
+/// A [RON](ron) error enriched with everything needed to find and fix it in the asset file.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub struct RonDiagnostic {
+    pub path: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    /// The field the error occurred in, if it could be determined.
+    pub field: Option<String>,
+    /// A likely fix, e.g. the correctly spelled name of an unknown field.
+    pub suggestion: Option<String>,
+    pub message: String,
+}
+
+impl Display for RonDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}",
+            self.path.display(),
+            self.line,
+            self.column,
+            self.message
+        )?;
+        if let Some(field) = &self.field {
+            write!(f, " (field `{field}`)")?;
+        }
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, ". Did you mean `{suggestion}`?")?;
+        }
+        Ok(())
+    }
+}
+
+impl RonDiagnostic {
+    /// Enrich `error`, which occurred while parsing `source` from the asset at `path`.
+    pub fn new(path: &Path, source: &[u8], error: SpannedError) -> Self {
+        let SpannedError { code, position } = error;
+        let (field, suggestion) = match &code {
+            RonError::NoSuchStructField {
+                expected, found, ..
+            } => (Some(found.clone()), closest_match(found, expected)),
+            RonError::NoSuchEnumVariant {
+                expected, found, ..
+            } => (
+                field_before(source, position.line, position.col),
+                closest_match(found, expected),
+            ),
+            RonError::MissingStructField { field, .. }
+            | RonError::DuplicateStructField { field, .. } => (Some(field.to_string()), None),
+            RonError::ExpectedDifferentStructName { expected, .. } => {
+                (None, Some(expected.to_string()))
+            }
+            _ => (field_before(source, position.line, position.col), None),
+        };
+        Self {
+            path: path.to_path_buf(),
+            line: position.line,
+            column: position.col,
+            field,
+            suggestion,
+            message: code.to_string(),
+        }
+    }
+}
+
+/// Find the name of the last field that was opened before the (1-based) `line` and `column`, i.e. the field whose value caused the error.
+fn field_before(source: &[u8], line: usize, column: usize) -> Option<String> {
+    let source = String::from_utf8_lossy(source);
+    let prefix_lines = source.split_inclusive('\n').take(line);
+    let mut prefix = String::new();
+    for (index, current_line) in prefix_lines.enumerate() {
+        if index + 1 == line {
+            prefix.extend(current_line.chars().take(column.saturating_sub(1)));
+        } else {
+            prefix.push_str(current_line);
+        }
+    }
+
+    let mut field = None;
+    let mut identifier = String::new();
+    let mut after_identifier = false;
+    let mut in_string = false;
+    let mut chars = prefix.chars().peekable();
+    while let Some(char) = chars.next() {
+        if in_string {
+            match char {
+                '\\' => {
+                    chars.next();
+                }
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match char {
+            '"' => {
+                in_string = true;
+                identifier.clear();
+            }
+            char if char.is_alphanumeric() || char == '_' => {
+                if after_identifier {
+                    identifier.clear();
+                    after_identifier = false;
+                }
+                identifier.push(char);
+            }
+            char if char.is_whitespace() => after_identifier = !identifier.is_empty(),
+            ':' if !identifier.is_empty() => {
+                field = Some(identifier.clone());
+                identifier.clear();
+                after_identifier = false;
+            }
+            _ => {
+                identifier.clear();
+                after_identifier = false;
+            }
+        }
+    }
+    field
+}
+
+/// The candidate most similar to `found`, if it is similar enough to likely be a typo.
+fn closest_match(found: &str, candidates: &[&str]) -> Option<String> {
+    let max_distance = usize::max(2, found.chars().count() / 3);
+    candidates
+        .iter()
+        .map(|candidate| (edit_distance(found, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.to_string())
+}
+
+/// The Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous_row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current_row = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous_row[j] + usize::from(a_char != *b_char);
+            let insertion = current_row[j] + 1;
+            let deletion = previous_row[j + 1] + 1;
+            current_row.push(substitution.min(insertion).min(deletion));
+        }
+        previous_row = current_row;
+    }
+    previous_row[b.len()]
+}
+
+#[cfg(test)]
+mod tests_ron_diagnostic {
+    use std::path::Path;
+
+    use ron::de::from_bytes;
+    use serde::Deserialize;
+
+    use super::{closest_match, edit_distance, RonDiagnostic};
+
+    #[allow(dead_code)]
+    #[derive(Deserialize, Debug)]
+    #[serde(deny_unknown_fields)]
+    struct Example {
+        combat_width: u8,
+        #[serde(default)]
+        defense_modifier: f32,
+    }
+
+    fn diagnose(source: &str) -> RonDiagnostic {
+        let error = from_bytes::<Example>(source.as_bytes()).unwrap_err();
+        RonDiagnostic::new(Path::new("example.ron"), source.as_bytes(), error)
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("combat_width", "combat_width"), 0);
+        assert_eq!(edit_distance("combat_widht", "combat_width"), 2);
+        assert_eq!(edit_distance("defense", "defence"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn test_closest_match() {
+        let candidates = ["combat_width", "defense_modifier"];
+        assert_eq!(
+            closest_match("combat_widht", &candidates),
+            Some("combat_width".to_string())
+        );
+        assert_eq!(
+            closest_match("defence_modifier", &candidates),
+            Some("defense_modifier".to_string())
+        );
+        assert_eq!(closest_match("attrition", &candidates), None);
+    }
+
+    #[test]
+    fn test_unknown_field() {
+        let diagnostic = diagnose("Example (\n    combat_widht: 8,\n)");
+        assert_eq!(diagnostic.line, 2);
+        assert_eq!(diagnostic.field.as_deref(), Some("combat_widht"));
+        assert_eq!(diagnostic.suggestion.as_deref(), Some("combat_width"));
+    }
+
+    #[test]
+    fn test_invalid_value() {
+        let diagnostic =
+            diagnose("Example (\n    combat_width: 8,\n    defense_modifier: \"high\",\n)");
+        assert_eq!(diagnostic.line, 3);
+        assert_eq!(diagnostic.field.as_deref(), Some("defense_modifier"));
+        assert_eq!(diagnostic.suggestion, None);
+    }
+
+    #[test]
+    fn test_missing_field() {
+        let diagnostic = diagnose("Example (\n    defense_modifier: 1.5,\n)");
+        assert_eq!(diagnostic.field.as_deref(), Some("combat_width"));
+    }
+}
@@ -0,0 +1,219 @@
+use std::path::{Path, PathBuf};
+
+use bevy::{
+    asset::{io::Reader, Asset, AssetLoader, AsyncReadExt, Handle, LoadContext},
+    reflect::Reflect,
+    render::texture::Image,
+};
+use ron::de::from_bytes;
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::{diagnostics::RonDiagnostic, directory_name, present};
+
+fn default_texture() -> PathBuf {
+    PathBuf::from("texture.png")
+}
+
+fn default_defense_modifier() -> f32 {
+    1.0
+}
+
+/// What an improvement can be built on.
+#[derive(Deserialize, Reflect, Copy, Debug, Clone, PartialEq, Eq)]
+pub enum Placement {
+    Tile,
+    Connection,
+}
+
+/// Something that can be built on a tile or connection, like a fort or a bridge.
+#[derive(Reflect, Asset, Debug, Clone, PartialEq)]
+pub struct ImprovementType {
+    /// Defaults to the name of the directory the improvement type is defined in.
+    display_name: String,
+    /// Drawn on top of the tile or connection. Improvements on connections are rotated like the texture of connection types.
+    texture: Handle<Image>,
+    placement: Placement,
+    /// The turns it takes to build the improvement.
+    build_turns: u8,
+    /// The production it costs to build the improvement.
+    cost: u16,
+    /// The damage the improvement takes from fighting on its tile before it is destroyed.
+    durability: u8,
+    /// Multiplies the defense of units on the tile.
+    defense_modifier: f32,
+    /// Added to the supply capacity of the tile.
+    supply_capacity: u8,
+    /// The supply the improvement sends out each turn for the faction controlling its tile, like a depot.
+    supply: u8,
+    /// Cancels the movement cost and combat penalty of the connection it is built on, like a bridge over a river.
+    negates_connection: bool,
+}
+
+impl ImprovementType {
+    pub fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    pub fn texture(&self) -> &Handle<Image> {
+        &self.texture
+    }
+
+    pub fn placement(&self) -> Placement {
+        self.placement
+    }
+
+    pub fn build_turns(&self) -> u8 {
+        self.build_turns
+    }
+
+    pub fn cost(&self) -> u16 {
+        self.cost
+    }
+
+    pub fn durability(&self) -> u8 {
+        self.durability
+    }
+
+    pub fn defense_modifier(&self) -> f32 {
+        self.defense_modifier
+    }
+
+    pub fn supply_capacity(&self) -> u8 {
+        self.supply_capacity
+    }
+
+    pub fn supply(&self) -> u8 {
+        self.supply
+    }
+
+    pub fn negates_connection(&self) -> bool {
+        self.negates_connection
+    }
+}
+
+/// An `improvement_type.ron` file as it is written.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename = "ImprovementType", deny_unknown_fields)]
+struct ImprovementTypeDefinition {
+    #[serde(default, deserialize_with = "present")]
+    display_name: Option<String>,
+    /// Relative to the directory of the definition.
+    #[serde(default = "default_texture")]
+    texture: PathBuf,
+    placement: Placement,
+    build_turns: u8,
+    cost: u16,
+    durability: u8,
+    #[serde(default = "default_defense_modifier")]
+    defense_modifier: f32,
+    #[serde(default)]
+    supply_capacity: u8,
+    #[serde(default)]
+    supply: u8,
+    #[serde(default)]
+    negates_connection: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct ImprovementTypeLoader;
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum ImprovementTypeLoaderError {
+    /// An [IO](std::io) Error
+    #[error("Could not read data of the improvement type at {}: {error}", path.display())]
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    /// A [RON](ron) Error
+    #[error("Invalid improvement type: {0}")]
+    Ron(#[from] RonDiagnostic),
+}
+
+impl AssetLoader for ImprovementTypeLoader {
+    type Asset = ImprovementType;
+    type Settings = ();
+    type Error = ImprovementTypeLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(|error| ImprovementTypeLoaderError::Io {
+                path: load_context.path().to_path_buf(),
+                error,
+            })?;
+        let ImprovementTypeDefinition {
+            display_name,
+            texture,
+            placement,
+            build_turns,
+            cost,
+            durability,
+            defense_modifier,
+            supply_capacity,
+            supply,
+            negates_connection,
+        } = from_bytes::<ImprovementTypeDefinition>(&bytes)
+            .map_err(|error| RonDiagnostic::new(load_context.path(), &bytes, error))?;
+        let directory = load_context
+            .path()
+            .parent()
+            .unwrap_or(Path::new(""))
+            .to_path_buf();
+        Ok(ImprovementType {
+            display_name: display_name.unwrap_or_else(|| directory_name(load_context)),
+            texture: load_context.load(directory.join(texture)),
+            placement,
+            build_turns,
+            cost,
+            durability,
+            defense_modifier,
+            supply_capacity,
+            supply,
+            negates_connection,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["improvement"]
+    }
+}
+
+#[cfg(test)]
+mod tests_improvement_type {
+    use ron::de::from_str;
+
+    use super::{ImprovementTypeDefinition, Placement};
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let definition = from_str::<ImprovementTypeDefinition>(
+            "ImprovementType (placement: Connection, build_turns: 2, cost: 30, durability: 40)",
+        )
+        .unwrap();
+        assert_eq!(definition.placement, Placement::Connection);
+        assert_eq!(definition.texture.to_str(), Some("texture.png"));
+        assert_eq!(definition.defense_modifier, 1.0);
+        assert_eq!(definition.supply_capacity, 0);
+        assert_eq!(definition.supply, 0);
+        assert!(!definition.negates_connection);
+    }
+
+    #[test]
+    fn test_display_name() {
+        let definition = from_str::<ImprovementTypeDefinition>(
+            "ImprovementType (display_name: \"Fort\", placement: Tile, build_turns: 3, cost: 60, durability: 30)",
+        )
+        .unwrap();
+        assert_eq!(definition.display_name.as_deref(), Some("Fort"));
+    }
+}
@@ -0,0 +1,91 @@
+use bevy::{asset::LoadContext, reflect::Reflect};
+use serde::{Deserialize, Deserializer};
+
+pub use autotile::{neighbour_mask, Neighbour};
+pub use connection_type::{ConnectionType, ConnectionTypeLoader};
+pub use diagnostics::RonDiagnostic;
+pub use improvement_type::{ImprovementType, ImprovementTypeLoader, Placement};
+pub use tile_type::{TileType, TileTypeLoader, TransitionTrigger};
+pub use unit_type::{Ability, Rank, UnitType, UnitTypeLoader};
+pub use weather_type::{WeatherType, WeatherTypeLoader};
+
+mod autotile;
+mod connection_type;
+mod diagnostics;
+mod improvement_type;
+mod texture_variants;
+mod tile_type;
+mod unit_type;
+mod weather_type;
+
+/// How a unit moves, which decides how costly terrain is to traverse.
+#[derive(Deserialize, Reflect, Copy, Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MovementClass {
+    #[default]
+    Foot,
+    Wheeled,
+    Tracked,
+}
+
+/// The movement cost to enter a tile for each [MovementClass].
+/// `None` means the tile is impassable for that class.
+#[derive(Deserialize, Reflect, Debug, Clone, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct MovementCosts {
+    foot: Option<u8>,
+    wheeled: Option<u8>,
+    tracked: Option<u8>,
+}
+
+impl Default for MovementCosts {
+    fn default() -> Self {
+        Self {
+            foot: Some(1),
+            wheeled: Some(1),
+            tracked: Some(1),
+        }
+    }
+}
+
+impl MovementCosts {
+    pub fn get(&self, movement_class: MovementClass) -> Option<u8> {
+        match movement_class {
+            MovementClass::Foot => self.foot,
+            MovementClass::Wheeled => self.wheeled,
+            MovementClass::Tracked => self.tracked,
+        }
+    }
+
+    /// Add `movement_cost` to the cost of every class the tile is passable for.
+    /// Entering a tile always costs at least one movement point.
+    pub fn adjusted(&self, movement_cost: i8) -> Self {
+        let adjust = |cost: Option<u8>| {
+            cost.map(|cost| {
+                (i16::from(cost) + i16::from(movement_cost)).clamp(1, i16::from(u8::MAX)) as u8
+            })
+        };
+        Self {
+            foot: adjust(self.foot),
+            wheeled: adjust(self.wheeled),
+            tracked: adjust(self.tracked),
+        }
+    }
+}
+
+/// The name of the directory an asset is defined in, e.g. `forest` for `tile_types/forest/tile_type.ron`.
+fn directory_name(load_context: &LoadContext) -> String {
+    load_context
+        .path()
+        .parent()
+        .and_then(|parent| parent.file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Deserialize a field that may be left out as `Some` without having to write `Some(...)` in RON.
+/// Use together with `#[serde(default)]`.
+fn present<'de, D: Deserializer<'de>, T: Deserialize<'de>>(
+    deserializer: D,
+) -> Result<Option<T>, D::Error> {
+    T::deserialize(deserializer).map(Some)
+}
@@ -0,0 +1,158 @@
+use std::path::PathBuf;
+
+use bevy::{
+    asset::Handle,
+    reflect::Reflect,
+    render::texture::Image,
+    sprite::{TextureAtlas, TextureAtlasLayout},
+};
+use serde::Deserialize;
+
+use crate::gameplay_plugin::{components::AxialCoordinates, random};
+
+use super::autotile::FRAME_SIZE;
+
+/// Alternative looks of a tile type, one of which is picked for each tile so that large areas of the same tile type don't look tiled.
+#[derive(Reflect, Default, Debug, Clone, PartialEq)]
+pub enum TextureVariants {
+    /// Every tile uses the texture of the tile type.
+    #[default]
+    Single,
+    /// One texture per variant.
+    Textures(Vec<Handle<Image>>),
+    /// The texture of the tile type is an atlas with one frame of the size of a tile per variant, read row by row.
+    Atlas {
+        layout: Handle<TextureAtlasLayout>,
+        frames: usize,
+    },
+}
+
+impl TextureVariants {
+    pub fn count(&self) -> usize {
+        match self {
+            TextureVariants::Single => 1,
+            TextureVariants::Textures(textures) => textures.len(),
+            TextureVariants::Atlas { frames, .. } => *frames,
+        }
+    }
+
+    /// The texture and the frame of the atlas to draw the variant `index` with.
+    /// `texture` is the texture of the tile type.
+    pub fn get(
+        &self,
+        index: usize,
+        texture: &Handle<Image>,
+    ) -> Option<(Handle<Image>, Option<TextureAtlas>)> {
+        match self {
+            TextureVariants::Single => (index == 0).then(|| (texture.clone(), None)),
+            TextureVariants::Textures(textures) => {
+                textures.get(index).map(|texture| (texture.clone(), None))
+            }
+            TextureVariants::Atlas { layout, frames } => (index < *frames).then(|| {
+                (
+                    texture.clone(),
+                    Some(TextureAtlas {
+                        layout: layout.clone(),
+                        index,
+                    }),
+                )
+            }),
+        }
+    }
+
+    /// The variant of the tile at `coordinates` on a map with the seed `seed`.
+    /// The same coordinates and seed always result in the same variant, so the choice is stable across saving and loading.
+    pub fn pick(&self, coordinates: AxialCoordinates, seed: u64) -> usize {
+        let coordinates =
+            (u64::from(coordinates.q() as u32) << 32) | u64::from(coordinates.r() as u32);
+        // Mixing spreads neighbouring coordinates over all variants.
+        let hash = random::mix((seed ^ coordinates).wrapping_add(random::GOLDEN_GAMMA));
+        (hash % self.count() as u64) as usize
+    }
+}
+
+/// The `texture_variants` of a `tile_type.ron` file as they are written.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub(super) enum TextureVariantsDefinition {
+    /// Relative to the directory of the definition, e.g. `["texture_0.png", "texture_1.png"]`.
+    Textures(Vec<PathBuf>),
+    Atlas {
+        columns: u32,
+        rows: u32,
+    },
+}
+
+impl TextureVariantsDefinition {
+    pub(super) fn atlas_layout(columns: u32, rows: u32) -> TextureAtlasLayout {
+        TextureAtlasLayout::from_grid(FRAME_SIZE, columns, rows, None, None)
+    }
+}
+
+#[cfg(test)]
+mod tests_texture_variants {
+    use std::collections::HashSet;
+
+    use bevy::asset::Handle;
+
+    use super::TextureVariants;
+    use crate::gameplay_plugin::components::AxialCoordinates;
+
+    fn textures(amount: usize) -> TextureVariants {
+        TextureVariants::Textures(vec![Handle::default(); amount])
+    }
+
+    #[test]
+    fn test_pick_is_stable() {
+        let variants = textures(4);
+        for q in -5..5 {
+            for r in -5..5 {
+                let coordinates = AxialCoordinates::new(q, r);
+                assert_eq!(
+                    variants.pick(coordinates, 42),
+                    variants.pick(coordinates, 42)
+                );
+                assert!(variants.pick(coordinates, 42) < 4);
+            }
+        }
+        assert_eq!(
+            TextureVariants::Single.pick(AxialCoordinates::new(3, -2), 7),
+            0
+        );
+    }
+
+    #[test]
+    fn test_pick_uses_every_variant() {
+        let variants = textures(4);
+        let picked: HashSet<_> = (-3..3)
+            .flat_map(|q| (-3..3).map(move |r| AxialCoordinates::new(q, r)))
+            .map(|coordinates| variants.pick(coordinates, 0))
+            .collect();
+        assert_eq!(picked.len(), 4);
+        // Another seed results in another pattern.
+        let coordinates = (-3..3).map(|q| AxialCoordinates::new(q, 1));
+        let first: Vec<_> = coordinates
+            .clone()
+            .map(|coordinates| variants.pick(coordinates, 0))
+            .collect();
+        let second: Vec<_> = coordinates
+            .map(|coordinates| variants.pick(coordinates, 1))
+            .collect();
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_get() {
+        let texture = Handle::default();
+        assert!(TextureVariants::Single.get(0, &texture).is_some());
+        assert!(TextureVariants::Single.get(1, &texture).is_none());
+        assert!(textures(2).get(1, &texture).is_some());
+        assert!(textures(2).get(2, &texture).is_none());
+        let atlas = TextureVariants::Atlas {
+            layout: Handle::default(),
+            frames: 3,
+        };
+        let (_, frame) = atlas.get(2, &texture).unwrap();
+        assert_eq!(frame.map(|frame| frame.index), Some(2));
+        assert!(atlas.get(3, &texture).is_none());
+    }
+}
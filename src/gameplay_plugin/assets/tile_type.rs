@@ -0,0 +1,662 @@
+use std::path::{Path, PathBuf};
+
+use bevy::{
+    asset::{
+        io::{AssetReaderError, Reader},
+        Asset, AssetLoader, AsyncReadExt, Handle, LoadContext, ReadAssetBytesError,
+    },
+    reflect::Reflect,
+    render::texture::Image,
+};
+use itertools::Itertools;
+use ron::de::from_bytes;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::gameplay_plugin::{
+    content_library::{ContentKind, LIBRARY_PATH},
+    save_file::TileTypePath,
+};
+
+use super::{
+    autotile::{Autotile, AutotileDefinition, AutotileError},
+    diagnostics::RonDiagnostic,
+    directory_name, present,
+    texture_variants::{TextureVariants, TextureVariantsDefinition},
+    MovementCosts,
+};
+
+const DEFAULT_TEXTURE: &str = "texture.png";
+const DEFAULT_DEFENSE_MODIFIER: f32 = 1.0;
+const DEFAULT_SUPPLY_CAPACITY: u8 = 1;
+const DEFAULT_MAX_ENTRENCHMENT: u8 = 2;
+
+#[derive(Reflect, Asset, Debug, Clone, PartialEq)]
+pub struct TileType {
+    /// Defaults to the name of the directory the tile type is defined in.
+    display_name: String,
+    /// An atlas if the tile type has an `autotile` or its texture variants are an atlas.
+    texture: Handle<Image>,
+    /// Takes precedence over the texture variants.
+    autotile: Option<Autotile>,
+    texture_variants: TextureVariants,
+    combat_width: u8,
+    movement_costs: MovementCosts,
+    /// Multiplies the defense of units on this tile.
+    defense_modifier: f32,
+    blocks_vision: bool,
+    /// Added to the vision range of units on this tile.
+    vision_bonus: u8,
+    /// The entrenchment units on this tile reach after staying on it for as many turns.
+    max_entrenchment: u8,
+    /// How much supply can flow through this tile per turn.
+    supply_capacity: u8,
+    /// The supply the tile sends out each turn for the faction controlling it, like a city.
+    supply: u8,
+    /// Supply flows onto this tile without losing any, like onto a road.
+    carries_supply: bool,
+    /// The share of health units on this tile lose each turn.
+    attrition_rate: f32,
+    /// How the tile type changes into other tile types.
+    transitions: Vec<TileTransition>,
+}
+
+/// A change of a tile into another tile type, e.g. `urban` turning into `ruins` after sustained fighting.
+#[derive(Reflect, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TileTransition {
+    trigger: TransitionTrigger,
+    /// The tile type the tile turns into.
+    tile_type: TileTypePath,
+    /// The production a faction pays to start the work on an event transition, like clearing a forest.
+    #[serde(default)]
+    cost: u16,
+    /// The turns the work on an event transition takes. `0` changes the tile right away.
+    #[serde(default)]
+    turns: u8,
+}
+
+impl TileTransition {
+    pub fn trigger(&self) -> &TransitionTrigger {
+        &self.trigger
+    }
+
+    pub fn tile_type(&self) -> &TileTypePath {
+        &self.tile_type
+    }
+
+    pub fn cost(&self) -> u16 {
+        self.cost
+    }
+
+    pub fn turns(&self) -> u8 {
+        self.turns
+    }
+}
+
+#[derive(Reflect, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum TransitionTrigger {
+    /// The fighting over the tile did at least `damage` since the tile got its tile type.
+    Fighting { damage: u16 },
+    /// A [crate::gameplay_plugin::events::TerrainEvent] with this name happened on the tile, e.g. `cleared` for a forest being cut down.
+    Event(String),
+}
+
+impl TileType {
+    pub fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    pub fn texture(&self) -> &Handle<Image> {
+        &self.texture
+    }
+
+    pub fn autotile(&self) -> Option<&Autotile> {
+        self.autotile.as_ref()
+    }
+
+    pub fn texture_variants(&self) -> &TextureVariants {
+        &self.texture_variants
+    }
+
+    pub fn combat_width(&self) -> u8 {
+        self.combat_width
+    }
+
+    pub fn movement_costs(&self) -> &MovementCosts {
+        &self.movement_costs
+    }
+
+    pub fn defense_modifier(&self) -> f32 {
+        self.defense_modifier
+    }
+
+    pub fn blocks_vision(&self) -> bool {
+        self.blocks_vision
+    }
+
+    pub fn vision_bonus(&self) -> u8 {
+        self.vision_bonus
+    }
+
+    pub fn max_entrenchment(&self) -> u8 {
+        self.max_entrenchment
+    }
+
+    pub fn supply_capacity(&self) -> u8 {
+        self.supply_capacity
+    }
+
+    pub fn supply(&self) -> u8 {
+        self.supply
+    }
+
+    pub fn carries_supply(&self) -> bool {
+        self.carries_supply
+    }
+
+    pub fn attrition_rate(&self) -> f32 {
+        self.attrition_rate
+    }
+
+    pub fn transitions(&self) -> &[TileTransition] {
+        &self.transitions
+    }
+
+    /// The tile type a tile of this type turns into after fighting did `damage` to it.
+    /// If several transitions are reached, the first one defined wins.
+    pub fn transition_after_fighting(&self, damage: u16) -> Option<&TileTypePath> {
+        self.transitions
+            .iter()
+            .find(|transition| match transition.trigger {
+                TransitionTrigger::Fighting { damage: threshold } => damage >= threshold,
+                TransitionTrigger::Event(_) => false,
+            })
+            .map(TileTransition::tile_type)
+    }
+
+    /// The transition of a tile of this type for the terrain event `event`.
+    pub fn event_transition(&self, event: &str) -> Option<&TileTransition> {
+        self.transitions.iter().find(|transition| {
+            matches!(&transition.trigger, TransitionTrigger::Event(name) if name == event)
+        })
+    }
+
+    /// The tile type a tile of this type turns into when the terrain event `event` happens on it.
+    pub fn transition_on_event(&self, event: &str) -> Option<&TileTypePath> {
+        self.event_transition(event).map(TileTransition::tile_type)
+    }
+}
+
+/// A `tile_type.ron` file as it is written.
+/// Every field is optional, because a definition may inherit the missing fields from its `base`.
+#[derive(Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename = "TileType", deny_unknown_fields)]
+struct TileTypeDefinition {
+    /// Another tile type to inherit all fields from that are not defined here.
+    #[serde(default, deserialize_with = "present")]
+    base: Option<TileTypePath>,
+    #[serde(default, deserialize_with = "present")]
+    display_name: Option<String>,
+    /// Relative to the directory of the definition. Defaults to `texture.png` if the definition has no base.
+    #[serde(default, deserialize_with = "present")]
+    texture: Option<PathBuf>,
+    #[serde(default, deserialize_with = "present")]
+    autotile: Option<AutotileDefinition>,
+    /// Replaces `texture` if the variants are separate textures.
+    #[serde(default, deserialize_with = "present")]
+    texture_variants: Option<TextureVariantsDefinition>,
+    #[serde(default, deserialize_with = "present")]
+    combat_width: Option<u8>,
+    #[serde(default, deserialize_with = "present")]
+    movement_costs: Option<MovementCosts>,
+    #[serde(default, deserialize_with = "present")]
+    defense_modifier: Option<f32>,
+    #[serde(default, deserialize_with = "present")]
+    blocks_vision: Option<bool>,
+    #[serde(default, deserialize_with = "present")]
+    vision_bonus: Option<u8>,
+    #[serde(default, deserialize_with = "present")]
+    max_entrenchment: Option<u8>,
+    #[serde(default, deserialize_with = "present")]
+    supply_capacity: Option<u8>,
+    #[serde(default, deserialize_with = "present")]
+    supply: Option<u8>,
+    #[serde(default, deserialize_with = "present")]
+    carries_supply: Option<bool>,
+    #[serde(default, deserialize_with = "present")]
+    attrition_rate: Option<f32>,
+    #[serde(default, deserialize_with = "present")]
+    transitions: Option<Vec<TileTransition>>,
+}
+
+impl TileTypeDefinition {
+    /// Parse the definition at `path` and make its texture path relative to the assets directory.
+    fn parse(path: &Path, bytes: &[u8]) -> Result<Self, RonDiagnostic> {
+        let mut definition = from_bytes::<TileTypeDefinition>(bytes)
+            .map_err(|error| RonDiagnostic::new(path, bytes, error))?;
+        let directory = path.parent().unwrap_or(Path::new(""));
+        definition.texture = match (definition.texture, &definition.base) {
+            (Some(texture), _) => Some(directory.join(texture)),
+            (None, None) => Some(directory.join(DEFAULT_TEXTURE)),
+            (None, Some(_)) => None,
+        };
+        if let Some(TextureVariantsDefinition::Textures(textures)) =
+            &mut definition.texture_variants
+        {
+            for texture in textures {
+                *texture = directory.join(&*texture);
+            }
+        }
+        Ok(definition)
+    }
+
+    /// Fill every field that is not defined here with the one of `base`.
+    fn inherit(self, base: TileTypeDefinition) -> Self {
+        Self {
+            base: base.base,
+            display_name: self.display_name.or(base.display_name),
+            texture: self.texture.or(base.texture),
+            autotile: self.autotile.or(base.autotile),
+            texture_variants: self.texture_variants.or(base.texture_variants),
+            combat_width: self.combat_width.or(base.combat_width),
+            movement_costs: self.movement_costs.or(base.movement_costs),
+            defense_modifier: self.defense_modifier.or(base.defense_modifier),
+            blocks_vision: self.blocks_vision.or(base.blocks_vision),
+            vision_bonus: self.vision_bonus.or(base.vision_bonus),
+            max_entrenchment: self.max_entrenchment.or(base.max_entrenchment),
+            supply_capacity: self.supply_capacity.or(base.supply_capacity),
+            supply: self.supply.or(base.supply),
+            carries_supply: self.carries_supply.or(base.carries_supply),
+            attrition_rate: self.attrition_rate.or(base.attrition_rate),
+            transitions: self.transitions.or(base.transitions),
+        }
+    }
+
+    /// Apply the defaults for every field that is still missing.
+    ///
+    /// ## Returns
+    /// - Err: The name of a required field if it is missing.
+    fn into_tile_type(
+        self,
+        display_name: String,
+        texture: Handle<Image>,
+        autotile: Option<Autotile>,
+        texture_variants: TextureVariants,
+    ) -> Result<TileType, &'static str> {
+        Ok(TileType {
+            display_name: self.display_name.unwrap_or(display_name),
+            texture,
+            autotile,
+            texture_variants,
+            combat_width: self.combat_width.ok_or("combat_width")?,
+            movement_costs: self.movement_costs.unwrap_or_default(),
+            defense_modifier: self.defense_modifier.unwrap_or(DEFAULT_DEFENSE_MODIFIER),
+            blocks_vision: self.blocks_vision.unwrap_or_default(),
+            vision_bonus: self.vision_bonus.unwrap_or_default(),
+            max_entrenchment: self.max_entrenchment.unwrap_or(DEFAULT_MAX_ENTRENCHMENT),
+            supply_capacity: self.supply_capacity.unwrap_or(DEFAULT_SUPPLY_CAPACITY),
+            supply: self.supply.unwrap_or_default(),
+            carries_supply: self.carries_supply.unwrap_or_default(),
+            attrition_rate: self.attrition_rate.unwrap_or_default(),
+            transitions: self.transitions.unwrap_or_default(),
+        })
+    }
+}
+
+/// The paths of the tile type `base` is referring to from the tile type at `path`, in the order they are looked up.
+/// Tile types are first looked up next to each other, e.g. `base: "forest"` in `tile_types/dense_forest/tile_type.ron` refers to `tile_types/forest/tile_type.ron`, and then in the library.
+/// A tile type with the same name as its base overrides the library entry, so it refers to the library directly.
+fn base_paths(path: &Path, base: &str) -> Vec<PathBuf> {
+    let tile_types_directory = path
+        .parent()
+        .and_then(|directory| directory.parent())
+        .unwrap_or(Path::new(""));
+    let sibling = tile_types_directory
+        .join(base)
+        .join(ContentKind::TileType.file_name());
+    let library_entry = PathBuf::from(ContentKind::TileType.path(LIBRARY_PATH, base));
+    if sibling == path || sibling == library_entry {
+        vec![library_entry]
+    } else {
+        vec![sibling, library_entry]
+    }
+}
+
+/// Load the texture of `definition`, if it has one.
+fn texture_handle(
+    load_context: &mut LoadContext,
+    definition: &TileTypeDefinition,
+) -> Handle<Image> {
+    match &definition.texture {
+        Some(texture) => load_context.load(texture.clone()),
+        None => Handle::default(),
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct TileTypeLoader;
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum TileTypeLoaderError {
+    /// An [IO](std::io) Error
+    #[error("Could not read data of the tile type at {}: {error}", path.display())]
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    /// A [RON](ron) Error
+    #[error("Invalid tile type: {0}")]
+    Ron(#[from] RonDiagnostic),
+    /// The base of a tile type could not be read.
+    #[error("Could not read the base `{base}` of the tile type at {}: {error}", path.display())]
+    Base {
+        path: PathBuf,
+        base: TileTypePath,
+        error: ReadAssetBytesError,
+    },
+    /// A tile type inherits from itself.
+    #[error("The tile types inherit from each other in a cycle: {}", chain.iter().map(|path| path.display()).join(" -> "))]
+    Cycle { chain: Vec<PathBuf> },
+    /// Neither the tile type nor any of its bases define a required field.
+    #[error("The tile type at {} is missing the field `{field}` and none of its bases define it", path.display())]
+    MissingField { path: PathBuf, field: &'static str },
+    /// The autotile of a tile type is invalid.
+    #[error("Invalid autotile of the tile type at {}: {error}", path.display())]
+    Autotile { path: PathBuf, error: AutotileError },
+    /// The texture variants of a tile type are empty.
+    #[error("The tile type at {} has no texture variants", path.display())]
+    NoTextureVariants { path: PathBuf },
+}
+
+impl AssetLoader for TileTypeLoader {
+    type Asset = TileType;
+    type Settings = ();
+    type Error = TileTypeLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let path = load_context.path().to_path_buf();
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(|error| TileTypeLoaderError::Io {
+                path: path.clone(),
+                error,
+            })?;
+        let mut definition = TileTypeDefinition::parse(&path, &bytes)?;
+
+        // Resolve the chain of bases. Reading them through the load context registers them as dependencies, so changing a base reloads every tile type inheriting from it.
+        let mut chain = vec![path.clone()];
+        while let Some(base) = definition.base.clone() {
+            let current_path = chain
+                .last()
+                .expect("BUG: The chain always contains the tile type itself.");
+            let candidates = base_paths(current_path, &base);
+            let last_candidate = candidates.len() - 1;
+            let mut found = None;
+            for (index, candidate) in candidates.into_iter().enumerate() {
+                match load_context.read_asset_bytes(candidate.clone()).await {
+                    Ok(bytes) => {
+                        found = Some((candidate, bytes));
+                        break;
+                    }
+                    // Fall back to the next candidate, e.g. the library.
+                    Err(ReadAssetBytesError::AssetReaderError(AssetReaderError::NotFound(_)))
+                        if index < last_candidate =>
+                    {
+                        continue
+                    }
+                    Err(error) => {
+                        return Err(TileTypeLoaderError::Base {
+                            path: current_path.clone(),
+                            base,
+                            error,
+                        })
+                    }
+                }
+            }
+            let (base_path, base_bytes) =
+                found.expect("BUG: Reading the last candidate either succeeds or returns.");
+            if chain.contains(&base_path) {
+                chain.push(base_path);
+                return Err(TileTypeLoaderError::Cycle { chain });
+            }
+            definition = definition.inherit(TileTypeDefinition::parse(&base_path, &base_bytes)?);
+            chain.push(base_path);
+        }
+
+        let (texture, texture_variants) = match definition.texture_variants.take() {
+            Some(TextureVariantsDefinition::Textures(textures)) => {
+                let textures: Vec<Handle<Image>> = textures
+                    .into_iter()
+                    .map(|texture| load_context.load(texture))
+                    .collect();
+                let Some(texture) = textures.first().cloned() else {
+                    return Err(TileTypeLoaderError::NoTextureVariants { path });
+                };
+                (texture, TextureVariants::Textures(textures))
+            }
+            Some(TextureVariantsDefinition::Atlas { columns, rows }) => {
+                if columns == 0 || rows == 0 {
+                    return Err(TileTypeLoaderError::NoTextureVariants { path });
+                }
+                let layout = load_context.add_labeled_asset(
+                    "texture_variants_layout".to_string(),
+                    TextureVariantsDefinition::atlas_layout(columns, rows),
+                );
+                let variants = TextureVariants::Atlas {
+                    layout,
+                    frames: columns as usize * rows as usize,
+                };
+                (texture_handle(load_context, &definition), variants)
+            }
+            None => (
+                texture_handle(load_context, &definition),
+                TextureVariants::Single,
+            ),
+        };
+        let autotile = match definition.autotile.take() {
+            Some(autotile) => {
+                let layout =
+                    load_context.add_labeled_asset("layout".to_string(), autotile.layout());
+                let autotile = autotile.into_autotile(layout).map_err(|error| {
+                    TileTypeLoaderError::Autotile {
+                        path: path.clone(),
+                        error,
+                    }
+                })?;
+                Some(autotile)
+            }
+            None => None,
+        };
+        definition
+            .into_tile_type(
+                directory_name(load_context),
+                texture,
+                autotile,
+                texture_variants,
+            )
+            .map_err(|field| TileTypeLoaderError::MissingField { path, field })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["tile"]
+    }
+}
+
+#[cfg(test)]
+mod tests_tile_type {
+    use std::path::{Path, PathBuf};
+
+    use bevy::asset::Handle;
+
+    use super::{base_paths, TextureVariants, TextureVariantsDefinition, TileTypeDefinition};
+    use crate::gameplay_plugin::assets::{MovementClass, MovementCosts};
+
+    fn parse(path: &str, source: &str) -> TileTypeDefinition {
+        TileTypeDefinition::parse(Path::new(path), source.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let tile_type = parse(
+            "tile_types/road/tile_type.ron",
+            "TileType (combat_width: 8)",
+        )
+        .into_tile_type(
+            "road".to_string(),
+            Handle::default(),
+            None,
+            TextureVariants::Single,
+        )
+        .unwrap();
+        assert_eq!(tile_type.display_name(), "road");
+        assert_eq!(tile_type.combat_width(), 8);
+        assert_eq!(tile_type.movement_costs(), &MovementCosts::default());
+        assert_eq!(tile_type.defense_modifier(), 1.0);
+        assert!(!tile_type.blocks_vision());
+        assert_eq!(tile_type.vision_bonus(), 0);
+        assert_eq!(tile_type.max_entrenchment(), 2);
+        assert_eq!(tile_type.supply_capacity(), 1);
+        assert_eq!(tile_type.supply(), 0);
+        assert!(!tile_type.carries_supply());
+        assert_eq!(tile_type.attrition_rate(), 0.0);
+        assert!(tile_type.transitions().is_empty());
+    }
+
+    #[test]
+    fn test_transitions() {
+        let urban = parse(
+            "tile_types/urban/tile_type.ron",
+            "TileType (combat_width: 32, transitions: [(trigger: Fighting(damage: 20), tile_type: \"ruins\"), (trigger: Fighting(damage: 10), tile_type: \"damaged_urban\"), (trigger: Event(\"burned\"), tile_type: \"ruins\", cost: 10, turns: 2)])",
+        )
+        .into_tile_type(
+            "urban".to_string(),
+            Handle::default(),
+            None,
+            TextureVariants::Single,
+        )
+        .unwrap();
+        assert_eq!(urban.transition_after_fighting(5), None);
+        assert_eq!(
+            urban.transition_after_fighting(10),
+            Some(&"damaged_urban".to_string())
+        );
+        // The first transition reached wins.
+        assert_eq!(
+            urban.transition_after_fighting(25),
+            Some(&"ruins".to_string())
+        );
+        assert_eq!(
+            urban.transition_on_event("burned"),
+            Some(&"ruins".to_string())
+        );
+        assert_eq!(urban.transition_on_event("cleared"), None);
+        let burning = urban.event_transition("burned").unwrap();
+        assert_eq!((burning.cost(), burning.turns()), (10, 2));
+        // Transitions through fighting cost nothing.
+        assert_eq!(urban.transitions()[0].cost(), 0);
+    }
+
+    #[test]
+    fn test_partial_movement_costs() {
+        let tile_type = parse(
+            "tile_types/road/tile_type.ron",
+            "TileType (combat_width: 8, movement_costs: (wheeled: None, tracked: Some(3)))",
+        )
+        .into_tile_type(
+            "road".to_string(),
+            Handle::default(),
+            None,
+            TextureVariants::Single,
+        )
+        .unwrap();
+        let movement_costs = tile_type.movement_costs();
+        assert_eq!(movement_costs.get(MovementClass::Foot), Some(1));
+        assert_eq!(movement_costs.get(MovementClass::Wheeled), None);
+        assert_eq!(movement_costs.get(MovementClass::Tracked), Some(3));
+    }
+
+    #[test]
+    fn test_missing_required_field() {
+        let result = parse("tile_types/road/tile_type.ron", "TileType ()").into_tile_type(
+            "road".to_string(),
+            Handle::default(),
+            None,
+            TextureVariants::Single,
+        );
+        assert_eq!(result, Err("combat_width"));
+    }
+
+    #[test]
+    fn test_texture_variants_are_relative_to_the_definition() {
+        let forest = parse(
+            "library/tile_types/forest/tile_type.ron",
+            "TileType (texture_variants: Textures([\"texture_0.png\", \"texture_1.png\"]), combat_width: 16)",
+        );
+        assert_eq!(
+            forest.texture_variants,
+            Some(TextureVariantsDefinition::Textures(vec![
+                PathBuf::from("library/tile_types/forest/texture_0.png"),
+                PathBuf::from("library/tile_types/forest/texture_1.png")
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_inherit() {
+        let forest = parse(
+            "scenario/tile_types/forest/tile_type.ron",
+            "TileType (display_name: \"Forest\", combat_width: 16, defense_modifier: 1.25, blocks_vision: true)",
+        );
+        let dense_forest = parse(
+            "scenario/tile_types/dense_forest/tile_type.ron",
+            "TileType (base: \"forest\", display_name: \"Dense Forest\", combat_width: 8)",
+        );
+        assert_eq!(
+            base_paths(
+                Path::new("scenario/tile_types/dense_forest/tile_type.ron"),
+                "forest"
+            ),
+            vec![
+                PathBuf::from("scenario/tile_types/forest/tile_type.ron"),
+                PathBuf::from("library/tile_types/forest/tile_type.ron")
+            ]
+        );
+        // Overriding a library entry.
+        assert_eq!(
+            base_paths(
+                Path::new("scenario/tile_types/forest/tile_type.ron"),
+                "forest"
+            ),
+            vec![PathBuf::from("library/tile_types/forest/tile_type.ron")]
+        );
+
+        let dense_forest = dense_forest.inherit(forest);
+        assert_eq!(dense_forest.base, None);
+        assert_eq!(
+            dense_forest.texture,
+            Some(PathBuf::from("scenario/tile_types/forest/texture.png"))
+        );
+
+        let dense_forest = dense_forest
+            .into_tile_type(
+                "dense_forest".to_string(),
+                Handle::default(),
+                None,
+                TextureVariants::Single,
+            )
+            .unwrap();
+        assert_eq!(dense_forest.display_name(), "Dense Forest");
+        assert_eq!(dense_forest.combat_width(), 8);
+        assert_eq!(dense_forest.defense_modifier(), 1.25);
+        assert!(dense_forest.blocks_vision());
+    }
+}
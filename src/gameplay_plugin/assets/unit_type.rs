@@ -0,0 +1,316 @@
+use std::path::{Path, PathBuf};
+
+use bevy::{
+    asset::{io::Reader, Asset, AssetLoader, AsyncReadExt, Handle, LoadContext},
+    reflect::Reflect,
+    render::texture::Image,
+};
+use ron::de::from_bytes;
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::{diagnostics::RonDiagnostic, directory_name, present, MovementClass};
+
+fn default_texture() -> PathBuf {
+    PathBuf::from("texture.png")
+}
+
+fn default_max_health() -> u8 {
+    100
+}
+
+fn default_max_organisation() -> u8 {
+    100
+}
+
+fn default_movement_points() -> u8 {
+    4
+}
+
+fn default_combat_width() -> u8 {
+    8
+}
+
+fn default_modifier() -> f32 {
+    1.0
+}
+
+/// Something a unit can do once it reached a [Rank].
+#[derive(Reflect, Deserialize, Copy, Debug, Clone, PartialEq, Eq)]
+pub enum Ability {
+    /// Digs in one more level each turn.
+    DigIn,
+    /// Lives off the land, so it suffers no more attrition out of supply than in supply.
+    Forage,
+}
+
+/// A rank a unit is promoted to once it gained enough experience in combat, e.g. `Veteran`.
+#[derive(Reflect, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Rank {
+    display_name: String,
+    /// The experience a unit needs to reach the rank.
+    experience: u16,
+    /// Multiplies the attack damage of the unit type.
+    #[serde(default = "default_modifier")]
+    attack_modifier: f32,
+    /// Multiplies the defense damage of the unit type.
+    #[serde(default = "default_modifier")]
+    defense_modifier: f32,
+    /// The abilities unlocked by the rank. Units keep the abilities of lower ranks.
+    #[serde(default)]
+    abilities: Vec<Ability>,
+}
+
+impl Rank {
+    pub fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    pub fn experience(&self) -> u16 {
+        self.experience
+    }
+
+    pub fn attack_modifier(&self) -> f32 {
+        self.attack_modifier
+    }
+
+    pub fn defense_modifier(&self) -> f32 {
+        self.defense_modifier
+    }
+
+    pub fn abilities(&self) -> &[Ability] {
+        &self.abilities
+    }
+}
+
+#[derive(Reflect, Asset, Debug, Clone, PartialEq)]
+pub struct UnitType {
+    /// Defaults to the name of the directory the unit type is defined in.
+    display_name: String,
+    texture: Handle<Image>,
+    max_health: u8,
+    max_organisation: u8,
+    attack_damage: u8,
+    defense_damage: u8,
+    movement_class: MovementClass,
+    /// The movement points the unit regains each turn.
+    movement_points: u8,
+    /// How much of the combat width of a tile the unit takes up when fighting over it.
+    combat_width: u8,
+    /// The ranks units are promoted to, sorted by the experience they need.
+    ranks: Vec<Rank>,
+}
+
+impl UnitType {
+    pub fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    pub fn texture(&self) -> &Handle<Image> {
+        &self.texture
+    }
+
+    pub fn max_health(&self) -> u8 {
+        self.max_health
+    }
+
+    pub fn max_organisation(&self) -> u8 {
+        self.max_organisation
+    }
+
+    pub fn attack_damage(&self) -> u8 {
+        self.attack_damage
+    }
+
+    pub fn defense_damage(&self) -> u8 {
+        self.defense_damage
+    }
+
+    pub fn movement_class(&self) -> MovementClass {
+        self.movement_class
+    }
+
+    pub fn movement_points(&self) -> u8 {
+        self.movement_points
+    }
+
+    pub fn combat_width(&self) -> u8 {
+        self.combat_width
+    }
+
+    pub fn ranks(&self) -> &[Rank] {
+        &self.ranks
+    }
+
+    /// The highest rank a unit with `experience` reached, if any.
+    pub fn rank(&self, experience: u16) -> Option<&Rank> {
+        self.ranks
+            .iter()
+            .take_while(|rank| rank.experience <= experience)
+            .last()
+    }
+
+    /// Whether a unit with `experience` unlocked `ability` with its rank or a lower one.
+    pub fn has_ability(&self, experience: u16, ability: Ability) -> bool {
+        self.ranks
+            .iter()
+            .take_while(|rank| rank.experience <= experience)
+            .any(|rank| rank.abilities.contains(&ability))
+    }
+}
+
+/// A `unit_type.ron` file as it is written.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename = "UnitType", deny_unknown_fields)]
+struct UnitTypeDefinition {
+    #[serde(default, deserialize_with = "present")]
+    display_name: Option<String>,
+    /// Relative to the directory of the definition.
+    #[serde(default = "default_texture")]
+    texture: PathBuf,
+    #[serde(default = "default_max_health")]
+    max_health: u8,
+    #[serde(default = "default_max_organisation")]
+    max_organisation: u8,
+    attack_damage: u8,
+    defense_damage: u8,
+    #[serde(default)]
+    movement_class: MovementClass,
+    #[serde(default = "default_movement_points")]
+    movement_points: u8,
+    #[serde(default = "default_combat_width")]
+    combat_width: u8,
+    #[serde(default)]
+    ranks: Vec<Rank>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct UnitTypeLoader;
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum UnitTypeLoaderError {
+    /// An [IO](std::io) Error
+    #[error("Could not read data of the unit type at {}: {error}", path.display())]
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    /// A [RON](ron) Error
+    #[error("Invalid unit type: {0}")]
+    Ron(#[from] RonDiagnostic),
+}
+
+impl AssetLoader for UnitTypeLoader {
+    type Asset = UnitType;
+    type Settings = ();
+    type Error = UnitTypeLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(|error| UnitTypeLoaderError::Io {
+                path: load_context.path().to_path_buf(),
+                error,
+            })?;
+        let UnitTypeDefinition {
+            display_name,
+            texture,
+            max_health,
+            max_organisation,
+            attack_damage,
+            defense_damage,
+            movement_class,
+            movement_points,
+            combat_width,
+            mut ranks,
+        } = from_bytes::<UnitTypeDefinition>(&bytes)
+            .map_err(|error| RonDiagnostic::new(load_context.path(), &bytes, error))?;
+        let directory = load_context
+            .path()
+            .parent()
+            .unwrap_or(Path::new(""))
+            .to_path_buf();
+        ranks.sort_by_key(Rank::experience);
+        Ok(UnitType {
+            display_name: display_name.unwrap_or_else(|| directory_name(load_context)),
+            texture: load_context.load(directory.join(texture)),
+            max_health,
+            max_organisation,
+            attack_damage,
+            defense_damage,
+            movement_class,
+            movement_points,
+            combat_width,
+            ranks,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["unit"]
+    }
+}
+
+#[cfg(test)]
+mod tests_unit_type {
+    use ron::de::from_str;
+
+    use super::{Ability, MovementClass, Rank, UnitType, UnitTypeDefinition};
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let unit_type =
+            from_str::<UnitTypeDefinition>("UnitType (attack_damage: 16, defense_damage: 12)")
+                .unwrap();
+        assert_eq!(unit_type.display_name, None);
+        assert_eq!(unit_type.texture.to_str(), Some("texture.png"));
+        assert_eq!(unit_type.attack_damage, 16);
+        assert_eq!(unit_type.defense_damage, 12);
+        assert_eq!(unit_type.max_health, 100);
+        assert_eq!(unit_type.max_organisation, 100);
+        assert_eq!(unit_type.movement_class, MovementClass::Foot);
+        assert_eq!(unit_type.movement_points, 4);
+        assert_eq!(unit_type.combat_width, 8);
+        assert!(unit_type.ranks.is_empty());
+    }
+
+    #[test]
+    fn test_ranks() {
+        let ranks = from_str::<Vec<Rank>>(
+            r#"[
+                (display_name: "Regular", experience: 10, attack_modifier: 1.1),
+                (display_name: "Veteran", experience: 30, defense_modifier: 1.2, abilities: [DigIn]),
+                (display_name: "Elite", experience: 60, abilities: [Forage]),
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(ranks[0].defense_modifier, 1.0);
+        let unit_type = UnitType {
+            display_name: "Light Infantry".to_string(),
+            texture: Default::default(),
+            max_health: 100,
+            max_organisation: 60,
+            attack_damage: 16,
+            defense_damage: 16,
+            movement_class: MovementClass::Foot,
+            movement_points: 4,
+            combat_width: 8,
+            ranks,
+        };
+        assert_eq!(unit_type.rank(9), None);
+        assert_eq!(unit_type.rank(10).map(Rank::display_name), Some("Regular"));
+        assert_eq!(unit_type.rank(59).map(Rank::display_name), Some("Veteran"));
+        assert!(!unit_type.has_ability(29, Ability::DigIn));
+        // Abilities of lower ranks are kept.
+        assert!(unit_type.has_ability(60, Ability::DigIn));
+        assert!(unit_type.has_ability(60, Ability::Forage));
+    }
+}
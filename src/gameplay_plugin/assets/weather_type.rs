@@ -0,0 +1,173 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use bevy::{
+    asset::{io::Reader, Asset, AssetLoader, AsyncReadExt, Handle, LoadContext},
+    reflect::Reflect,
+    render::texture::Image,
+};
+use ron::de::from_bytes;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::gameplay_plugin::save_file::TileTypePath;
+
+use super::{diagnostics::RonDiagnostic, directory_name, present, MovementCosts};
+
+fn default_overlay() -> PathBuf {
+    PathBuf::from("overlay.png")
+}
+
+fn default_combat_width_modifier() -> f32 {
+    1.0
+}
+
+/// Weather like mud or snow, which temporarily changes the properties of the tiles it lies on.
+#[derive(Reflect, Asset, Debug, Clone, PartialEq)]
+pub struct WeatherType {
+    /// Defaults to the name of the directory the weather type is defined in.
+    display_name: String,
+    /// Drawn on top of every tile with this weather.
+    overlay: Handle<Image>,
+    /// Added to the movement costs of passable tiles, e.g. for mud.
+    movement_cost: i8,
+    /// Multiplies the combat width of tiles, e.g. for snow.
+    combat_width_modifier: f32,
+    /// Replaces the movement costs of tiles by their tile type, e.g. to make frozen water passable.
+    /// `movement_cost` is not added to these.
+    tile_movement_costs: HashMap<TileTypePath, MovementCosts>,
+}
+
+impl WeatherType {
+    pub fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    pub fn overlay(&self) -> &Handle<Image> {
+        &self.overlay
+    }
+
+    pub fn movement_cost(&self) -> i8 {
+        self.movement_cost
+    }
+
+    pub fn combat_width_modifier(&self) -> f32 {
+        self.combat_width_modifier
+    }
+
+    pub fn tile_movement_costs(&self) -> &HashMap<TileTypePath, MovementCosts> {
+        &self.tile_movement_costs
+    }
+}
+
+/// A `weather_type.ron` file as it is written.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename = "WeatherType", deny_unknown_fields)]
+struct WeatherTypeDefinition {
+    #[serde(default, deserialize_with = "present")]
+    display_name: Option<String>,
+    /// Relative to the directory of the definition.
+    #[serde(default = "default_overlay")]
+    overlay: PathBuf,
+    #[serde(default)]
+    movement_cost: i8,
+    #[serde(default = "default_combat_width_modifier")]
+    combat_width_modifier: f32,
+    #[serde(default)]
+    tile_movement_costs: HashMap<TileTypePath, MovementCosts>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct WeatherTypeLoader;
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum WeatherTypeLoaderError {
+    /// An [IO](std::io) Error
+    #[error("Could not read data of the weather type at {}: {error}", path.display())]
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    /// A [RON](ron) Error
+    #[error("Invalid weather type: {0}")]
+    Ron(#[from] RonDiagnostic),
+}
+
+impl AssetLoader for WeatherTypeLoader {
+    type Asset = WeatherType;
+    type Settings = ();
+    type Error = WeatherTypeLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(|error| WeatherTypeLoaderError::Io {
+                path: load_context.path().to_path_buf(),
+                error,
+            })?;
+        let WeatherTypeDefinition {
+            display_name,
+            overlay,
+            movement_cost,
+            combat_width_modifier,
+            tile_movement_costs,
+        } = from_bytes::<WeatherTypeDefinition>(&bytes)
+            .map_err(|error| RonDiagnostic::new(load_context.path(), &bytes, error))?;
+        let directory = load_context
+            .path()
+            .parent()
+            .unwrap_or(Path::new(""))
+            .to_path_buf();
+        Ok(WeatherType {
+            display_name: display_name.unwrap_or_else(|| directory_name(load_context)),
+            overlay: load_context.load(directory.join(overlay)),
+            movement_cost,
+            combat_width_modifier,
+            tile_movement_costs,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["weather"]
+    }
+}
+
+#[cfg(test)]
+mod tests_weather_type {
+    use ron::de::from_str;
+
+    use super::WeatherTypeDefinition;
+    use crate::gameplay_plugin::assets::MovementClass;
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let definition = from_str::<WeatherTypeDefinition>("WeatherType ()").unwrap();
+        assert_eq!(definition.display_name, None);
+        assert_eq!(definition.overlay.to_str(), Some("overlay.png"));
+        assert_eq!(definition.movement_cost, 0);
+        assert_eq!(definition.combat_width_modifier, 1.0);
+        assert!(definition.tile_movement_costs.is_empty());
+    }
+
+    #[test]
+    fn test_tile_movement_costs() {
+        let definition = from_str::<WeatherTypeDefinition>(
+            "WeatherType (tile_movement_costs: {\"water\": (foot: Some(2))})",
+        )
+        .unwrap();
+        let water = &definition.tile_movement_costs["water"];
+        assert_eq!(water.get(MovementClass::Foot), Some(2));
+        // Classes left out keep the default cost.
+        assert_eq!(water.get(MovementClass::Tracked), Some(1));
+    }
+}
@@ -0,0 +1,437 @@
+//! How attacks onto a tile are resolved.
+//!
+//! Attackers on adjacent tiles engage all units on the defending tile. Only as many units fight on each side as fit into the combat width of the defending tile.
+//! The damage dealt is shared evenly among the fighting units of the other side and reduces their organisation and, to a lesser degree, their health.
+//! Entrenched units take less of their share.
+//! The units that fought gain experience, more so the more damage their side dealt.
+
+use std::ops::RangeInclusive;
+
+/// The share of damage that is lost as organisation.
+const ORGANISATION_LOSS_PER_DAMAGE: f32 = 1.0;
+/// The share of damage that is lost as health.
+const HEALTH_LOSS_PER_DAMAGE: f32 = 0.5;
+/// The share of the damage dealt that damages the defending tile and its improvements.
+const TILE_DAMAGE_PER_DAMAGE: f32 = 0.25;
+/// How much more or less damage a side deals with the best or worst luck.
+const DAMAGE_VARIANCE: f32 = 0.25;
+/// How much each level of entrenchment multiplies the defense of a unit by, on top of the first.
+const DEFENSE_PER_ENTRENCHMENT: f32 = 0.15;
+/// The experience every unit that fought gains.
+const EXPERIENCE_PER_COMBAT: u16 = 2;
+/// The experience gained per damage dealt by a side, shared among the units that fought.
+const EXPERIENCE_PER_DAMAGE: f32 = 0.5;
+/// How many evenly spread luck rolls per side a preview resolves the combat with.
+const PREVIEW_ROLLS: u16 = 16;
+
+/// The luck of both sides of a combat, each in `[0, 1)`.
+/// They are drawn from the [GameRng](super::resources::GameRng), so the same seed always results in the same combats.
+#[derive(Copy, Debug, Clone, PartialEq)]
+pub struct Rolls {
+    pub attack: f32,
+    pub defense: f32,
+}
+
+impl Rolls {
+    /// Neither side is lucky.
+    pub const AVERAGE: Rolls = Rolls {
+        attack: 0.5,
+        defense: 0.5,
+    };
+}
+
+/// Multiplies the damage of a side with the luck `roll`.
+fn luck(roll: f32) -> f32 {
+    1.0 + (roll * 2.0 - 1.0) * DAMAGE_VARIANCE
+}
+
+/// A unit that may take part in a combat.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Combatant {
+    /// The damage the unit deals at full health, already multiplied by the modifiers of its side.
+    pub damage: f32,
+    /// The combat width the unit takes up.
+    pub combat_width: u8,
+    pub health: u8,
+    pub max_health: u8,
+    pub organisation: u8,
+    /// How well the unit has dug in. Attackers leave their positions, so theirs doesn't count.
+    pub entrenchment: u8,
+}
+
+impl Combatant {
+    /// Units deal less damage the more health they lost.
+    pub fn effective_damage(&self) -> f32 {
+        if self.max_health == 0 {
+            return 0.0;
+        }
+        self.damage * f32::from(self.health) / f32::from(self.max_health)
+    }
+
+    /// Divides the damage the unit takes.
+    pub fn defense(&self) -> f32 {
+        1.0 + f32::from(self.entrenchment) * DEFENSE_PER_ENTRENCHMENT
+    }
+}
+
+/// The health and organisation a unit lost in a combat or to attrition.
+#[derive(Copy, Default, Debug, Clone, PartialEq, Eq)]
+pub struct Losses {
+    pub health: u8,
+    pub organisation: u8,
+}
+
+/// The outcome of a combat. The losses and experience are in the order of the combatants.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CombatResult {
+    pub attacker_losses: Vec<Losses>,
+    pub defender_losses: Vec<Losses>,
+    pub attacker_experience: Vec<u16>,
+    pub defender_experience: Vec<u16>,
+    /// The damage the attackers dealt, before it was shared among the defenders.
+    pub damage_dealt: f32,
+}
+
+impl CombatResult {
+    /// The damage the fighting did to the defending tile and its improvements.
+    pub fn tile_damage(&self) -> u8 {
+        (self.damage_dealt * TILE_DAMAGE_PER_DAMAGE)
+            .round()
+            .clamp(0.0, f32::from(u8::MAX)) as u8
+    }
+}
+
+/// How many of the `combatants` take part in a combat with `combat_width`, taking them in order.
+/// The first one always fights, even if it is wider than the combat width.
+pub fn participants(combatants: &[Combatant], combat_width: u8) -> usize {
+    let mut used_width = 0u16;
+    combatants
+        .iter()
+        .take_while(|combatant| {
+            used_width += u16::from(combatant.combat_width);
+            used_width <= u16::from(combat_width)
+        })
+        .count()
+        .max(combatants.len().min(1))
+}
+
+/// The losses of the `combatants` if `damage` is shared evenly among the ones taking part, reduced by their defense.
+fn share_damage(combatants: &[Combatant], participants: usize, damage: f32) -> Vec<Losses> {
+    let share = damage / participants.max(1) as f32;
+    combatants
+        .iter()
+        .enumerate()
+        .map(|(index, combatant)| {
+            if index >= participants {
+                return Losses::default();
+            }
+            let share = share / combatant.defense();
+            let loss = |multiplier: f32, current: u8| {
+                ((share * multiplier).round().max(0.0) as u8).min(current)
+            };
+            Losses {
+                health: loss(HEALTH_LOSS_PER_DAMAGE, combatant.health),
+                organisation: loss(ORGANISATION_LOSS_PER_DAMAGE, combatant.organisation),
+            }
+        })
+        .collect()
+}
+
+/// The experience each of `amount` combatants gains if `participants` of them fought and their side dealt `damage`.
+fn share_experience(amount: usize, participants: usize, damage: f32) -> Vec<u16> {
+    let share = damage / participants.max(1) as f32 * EXPERIENCE_PER_DAMAGE;
+    let experience = EXPERIENCE_PER_COMBAT.saturating_add(share.round().max(0.0) as u16);
+    (0..amount)
+        .map(|index| if index < participants { experience } else { 0 })
+        .collect()
+}
+
+/// Resolve a combat on a tile with `combat_width` whose defense is multiplied by `defense_modifier`.
+/// The combatants should be sorted by who should fight first.
+pub fn resolve(
+    attackers: &[Combatant],
+    defenders: &[Combatant],
+    combat_width: u8,
+    defense_modifier: f32,
+    rolls: Rolls,
+) -> CombatResult {
+    let fighting_attackers = participants(attackers, combat_width);
+    let fighting_defenders = participants(defenders, combat_width);
+    let attack: f32 = attackers[..fighting_attackers]
+        .iter()
+        .map(Combatant::effective_damage)
+        .sum::<f32>()
+        * luck(rolls.attack);
+    let defense: f32 = defenders[..fighting_defenders]
+        .iter()
+        .map(Combatant::effective_damage)
+        .sum::<f32>()
+        * luck(rolls.defense);
+    let damage_dealt = if defense_modifier > 0.0 {
+        attack / defense_modifier
+    } else {
+        attack
+    };
+    CombatResult {
+        attacker_losses: share_damage(attackers, fighting_attackers, defense),
+        defender_losses: share_damage(defenders, fighting_defenders, damage_dealt),
+        attacker_experience: share_experience(attackers.len(), fighting_attackers, damage_dealt),
+        defender_experience: share_experience(defenders.len(), fighting_defenders, defense),
+        damage_dealt,
+    }
+}
+
+/// The least and most health and organisation one side of a combat may lose, summed over its units.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LossRange {
+    pub health: RangeInclusive<u16>,
+    pub organisation: RangeInclusive<u16>,
+}
+
+impl LossRange {
+    fn new(losses: &[Losses]) -> Self {
+        let health = losses.iter().map(|losses| u16::from(losses.health)).sum();
+        let organisation = losses
+            .iter()
+            .map(|losses| u16::from(losses.organisation))
+            .sum();
+        Self {
+            health: health..=health,
+            organisation: organisation..=organisation,
+        }
+    }
+
+    fn extend(&mut self, other: &LossRange) {
+        let extend = |range: &RangeInclusive<u16>, other: &RangeInclusive<u16>| {
+            *range.start().min(other.start())..=*range.end().max(other.end())
+        };
+        self.health = extend(&self.health, &other.health);
+        self.organisation = extend(&self.organisation, &other.organisation);
+    }
+}
+
+/// The possible outcomes of a combat that has not happened yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CombatPreview {
+    pub attacker_losses: LossRange,
+    pub defender_losses: LossRange,
+    /// The chance in `[0, 1]` that at least one defender runs out of organisation and has to retreat.
+    pub defender_retreat_chance: f32,
+}
+
+/// Estimate the outcome of a combat by resolving it for evenly spread luck rolls of both sides, without drawing from the [GameRng](super::resources::GameRng).
+/// The arguments are the same as for [resolve()].
+pub fn preview(
+    attackers: &[Combatant],
+    defenders: &[Combatant],
+    combat_width: u8,
+    defense_modifier: f32,
+) -> CombatPreview {
+    let rolls = (0..PREVIEW_ROLLS).map(|roll| (f32::from(roll) + 0.5) / f32::from(PREVIEW_ROLLS));
+    let mut preview: Option<CombatPreview> = None;
+    let mut retreats = 0u16;
+    for attack in rolls.clone() {
+        for defense in rolls.clone() {
+            let result = resolve(
+                attackers,
+                defenders,
+                combat_width,
+                defense_modifier,
+                Rolls { attack, defense },
+            );
+            // Defenders which don't fight can't be forced to retreat.
+            let retreat = defenders
+                .iter()
+                .take(participants(defenders, combat_width))
+                .zip(&result.defender_losses)
+                .any(|(defender, losses)| {
+                    losses.organisation == defender.organisation && losses.health < defender.health
+                });
+            if retreat {
+                retreats += 1;
+            }
+            let attacker_losses = LossRange::new(&result.attacker_losses);
+            let defender_losses = LossRange::new(&result.defender_losses);
+            match &mut preview {
+                Some(preview) => {
+                    preview.attacker_losses.extend(&attacker_losses);
+                    preview.defender_losses.extend(&defender_losses);
+                }
+                None => {
+                    preview = Some(CombatPreview {
+                        attacker_losses,
+                        defender_losses,
+                        defender_retreat_chance: 0.0,
+                    })
+                }
+            }
+        }
+    }
+    let mut preview = preview.expect("BUG: A preview resolves the combat at least once.");
+    preview.defender_retreat_chance =
+        f32::from(retreats) / f32::from(PREVIEW_ROLLS * PREVIEW_ROLLS);
+    preview
+}
+
+#[cfg(test)]
+mod tests_combat {
+    use super::{participants, preview, resolve, Combatant, Losses, Rolls};
+
+    fn combatant(damage: f32, combat_width: u8) -> Combatant {
+        Combatant {
+            damage,
+            combat_width,
+            health: 100,
+            max_health: 100,
+            organisation: 60,
+            entrenchment: 0,
+        }
+    }
+
+    #[test]
+    fn test_participants() {
+        let combatants = vec![combatant(10.0, 8); 5];
+        assert_eq!(participants(&combatants, 16), 2);
+        assert_eq!(participants(&combatants, 20), 2);
+        assert_eq!(participants(&combatants, 64), 5);
+        // A single unit always fits.
+        assert_eq!(participants(&[combatant(10.0, 16)], 8), 1);
+        assert_eq!(participants(&[], 8), 0);
+    }
+
+    #[test]
+    fn test_resolve() {
+        let attackers = vec![combatant(20.0, 8); 3];
+        let defenders = vec![combatant(10.0, 8)];
+        let result = resolve(&attackers, &defenders, 16, 2.0, Rolls::AVERAGE);
+        // Two attackers fit the combat width and deal half their damage against the defense modifier.
+        assert_eq!(result.damage_dealt, 20.0);
+        assert_eq!(
+            result.defender_losses,
+            vec![Losses {
+                health: 10,
+                organisation: 20
+            }]
+        );
+        // The defense is shared by the attackers that fought.
+        assert_eq!(
+            result.attacker_losses,
+            vec![
+                Losses {
+                    health: 3,
+                    organisation: 5
+                },
+                Losses {
+                    health: 3,
+                    organisation: 5
+                },
+                Losses::default()
+            ]
+        );
+        assert_eq!(result.tile_damage(), 5);
+        // Units that fought gain experience for the damage their side dealt.
+        assert_eq!(result.attacker_experience, vec![7, 7, 0]);
+        assert_eq!(result.defender_experience, vec![7]);
+    }
+
+    #[test]
+    fn test_losses_are_capped() {
+        let attackers = vec![combatant(500.0, 8)];
+        let mut defender = combatant(10.0, 8);
+        defender.health = 40;
+        let result = resolve(&attackers, &[defender], 8, 1.0, Rolls::AVERAGE);
+        assert_eq!(
+            result.defender_losses,
+            vec![Losses {
+                health: 40,
+                organisation: 60
+            }]
+        );
+    }
+
+    #[test]
+    fn test_damaged_units_deal_less_damage() {
+        let mut attacker = combatant(20.0, 8);
+        attacker.health = 50;
+        let result = resolve(&[attacker], &[combatant(10.0, 8)], 8, 1.0, Rolls::AVERAGE);
+        assert_eq!(result.damage_dealt, 10.0);
+    }
+
+    #[test]
+    fn test_entrenchment() {
+        let attackers = [combatant(40.0, 8)];
+        let mut entrenched = combatant(10.0, 8);
+        entrenched.entrenchment = 2;
+        let result = resolve(
+            &attackers,
+            &[entrenched, combatant(10.0, 8)],
+            16,
+            1.0,
+            Rolls::AVERAGE,
+        );
+        // Both share the damage, but the entrenched one takes less of its share.
+        assert_eq!(
+            result.defender_losses,
+            vec![
+                Losses {
+                    health: 8,
+                    organisation: 15
+                },
+                Losses {
+                    health: 10,
+                    organisation: 20
+                }
+            ]
+        );
+        assert_eq!(result.damage_dealt, 40.0);
+    }
+
+    #[test]
+    fn test_luck() {
+        let attackers = [combatant(20.0, 8)];
+        let defenders = [combatant(20.0, 8)];
+        let unlucky = Rolls {
+            attack: 0.0,
+            defense: 0.0,
+        };
+        let result = resolve(&attackers, &defenders, 8, 1.0, unlucky);
+        assert_eq!(result.damage_dealt, 15.0);
+        let lucky = Rolls {
+            attack: 0.99,
+            defense: 0.5,
+        };
+        let result = resolve(&attackers, &defenders, 8, 1.0, lucky);
+        assert!(result.damage_dealt > 24.0 && result.damage_dealt < 25.0);
+    }
+
+    #[test]
+    fn test_preview() {
+        let attackers = [combatant(20.0, 8)];
+        let defenders = [combatant(20.0, 8)];
+        let outcome = preview(&attackers, &defenders, 8, 1.0);
+        // The average outcome lies within the previewed range.
+        let average = resolve(&attackers, &defenders, 8, 1.0, Rolls::AVERAGE);
+        assert!(outcome
+            .defender_losses
+            .organisation
+            .contains(&u16::from(average.defender_losses[0].organisation)));
+        assert!(
+            outcome.defender_losses.organisation.start()
+                < outcome.defender_losses.organisation.end()
+        );
+        assert_eq!(outcome.defender_retreat_chance, 0.0);
+
+        // A defender with little organisation left is likely to retreat.
+        let mut shaken = combatant(20.0, 8);
+        shaken.organisation = 18;
+        let outcome = preview(&attackers, &[shaken], 8, 1.0);
+        assert!(outcome.defender_retreat_chance > 0.5);
+        assert!(outcome.defender_retreat_chance < 1.0);
+
+        // A defender without organisation left beyond the combat width doesn't count as retreating.
+        let mut reserve = combatant(20.0, 8);
+        reserve.organisation = 0;
+        let outcome = preview(&attackers, &[combatant(20.0, 8), reserve], 8, 1.0);
+        assert_eq!(outcome.defender_retreat_chance, 0.0);
+    }
+}
@@ -1,19 +1,35 @@
+use std::collections::VecDeque;
+
 use bevy::{asset::Handle, ecs::entity::Entity, math::Vec2, prelude::Component, reflect::Reflect};
-use serde::{Deserialize, Serialize};
 
-use super::assets;
+use super::{
+    assets,
+    save_file::{
+        ConnectionTypePath, Echelon, FactionId, FormationId, ImprovementTypePath, TileTypePath,
+        UnitTypePath,
+    },
+};
 
 pub type TileConnectionEntity = Entity;
 pub type TileEntity = Entity;
 
 #[derive(Reflect, Component, Default, Debug, Clone, PartialEq, Eq)]
 pub struct TileType {
+    tile_type: TileTypePath,
     tile_type_data: Handle<assets::TileType>,
 }
 
 impl TileType {
-    pub fn new(tile_type_data: Handle<assets::TileType>) -> Self {
-        Self { tile_type_data }
+    pub fn new(tile_type: TileTypePath, tile_type_data: Handle<assets::TileType>) -> Self {
+        Self {
+            tile_type,
+            tile_type_data,
+        }
+    }
+
+    /// The id the tile type is referenced by in save files.
+    pub fn tile_type(&self) -> &TileTypePath {
+        &self.tile_type
     }
 
     pub fn tile_type_data(&self) -> &Handle<assets::TileType> {
@@ -21,16 +37,370 @@ impl TileType {
     }
 }
 
-// TODO!
+/// The height of a tile in levels above the lowest terrain.
+/// See [crate::gameplay_plugin::elevation] for its effects.
+#[derive(Copy, Component, Reflect, Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Elevation(u8);
+
+impl Elevation {
+    pub fn new(level: u8) -> Self {
+        Self(level)
+    }
+
+    pub fn level(&self) -> u8 {
+        self.0
+    }
+}
+
+/// Overrides the texture variant of a tile that is otherwise picked from its coordinates and the [crate::gameplay_plugin::resources::MapSeed].
+/// Ignored if the tile type has fewer variants.
+#[derive(Copy, Component, Reflect, Debug, Clone, PartialEq, Eq)]
+pub struct TextureVariant(pub u8);
+
+/// The damage fighting did to a tile since it got its tile type, which may trigger a transition of its tile type.
+#[derive(Copy, Component, Reflect, Default, Debug, Clone, PartialEq, Eq)]
+pub struct TerrainDamage(pub u16);
+
+/// Work on a tile which makes the terrain event `event` happen on it once no turns remain, like clearing a forest.
+#[derive(Component, Reflect, Default, Debug, Clone, PartialEq, Eq)]
+pub struct TerrainWork {
+    event: String,
+    remaining_turns: u8,
+}
+
+impl TerrainWork {
+    pub fn new(event: String, remaining_turns: u8) -> Self {
+        Self {
+            event,
+            remaining_turns,
+        }
+    }
+
+    pub fn event(&self) -> &str {
+        &self.event
+    }
+
+    pub fn remaining_turns(&self) -> u8 {
+        self.remaining_turns
+    }
+
+    /// Continue the work for one turn.
+    ///
+    /// ## Returns
+    /// Whether the work is done.
+    pub fn work(&mut self) -> bool {
+        self.remaining_turns = self.remaining_turns.saturating_sub(1);
+        self.remaining_turns == 0
+    }
+}
+
+/// Draws the weather of its parent tile.
+#[derive(Component, Reflect, Default, Debug, Clone, PartialEq, Eq)]
+pub struct WeatherOverlay;
+
+/// The connection type of a tile connection. Connections without one are plain borders.
+#[derive(Reflect, Component, Default, Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionType {
+    connection_type: ConnectionTypePath,
+    connection_type_data: Handle<assets::ConnectionType>,
+}
+
+impl ConnectionType {
+    pub fn new(
+        connection_type: ConnectionTypePath,
+        connection_type_data: Handle<assets::ConnectionType>,
+    ) -> Self {
+        Self {
+            connection_type,
+            connection_type_data,
+        }
+    }
+
+    /// The id the connection type is referenced by in save files.
+    pub fn connection_type(&self) -> &ConnectionTypePath {
+        &self.connection_type
+    }
+
+    pub fn connection_type_data(&self) -> &Handle<assets::ConnectionType> {
+        &self.connection_type_data
+    }
+}
+
+/// An improvement built or under construction on the tile or tile connection that is its parent.
+#[derive(Reflect, Component, Default, Debug, Clone, PartialEq, Eq)]
+pub struct Improvement {
+    improvement_type: ImprovementTypePath,
+    improvement_type_data: Handle<assets::ImprovementType>,
+    /// `0` once the improvement is built.
+    remaining_build_turns: u8,
+    lost_durability: u8,
+}
+
+impl Improvement {
+    pub fn new(
+        improvement_type: ImprovementTypePath,
+        improvement_type_data: Handle<assets::ImprovementType>,
+        remaining_build_turns: u8,
+        lost_durability: u8,
+    ) -> Self {
+        Self {
+            improvement_type,
+            improvement_type_data,
+            remaining_build_turns,
+            lost_durability,
+        }
+    }
+
+    /// The id the improvement type is referenced by in save files.
+    pub fn improvement_type(&self) -> &ImprovementTypePath {
+        &self.improvement_type
+    }
+
+    pub fn improvement_type_data(&self) -> &Handle<assets::ImprovementType> {
+        &self.improvement_type_data
+    }
+
+    pub fn remaining_build_turns(&self) -> u8 {
+        self.remaining_build_turns
+    }
+
+    pub fn lost_durability(&self) -> u8 {
+        self.lost_durability
+    }
+
+    /// Only built improvements have an effect.
+    pub fn is_built(&self) -> bool {
+        self.remaining_build_turns == 0
+    }
+
+    /// Continue the construction for one turn.
+    ///
+    /// ## Returns
+    /// Whether the improvement was completed by this.
+    pub fn build(&mut self) -> bool {
+        if self.is_built() {
+            return false;
+        }
+        self.remaining_build_turns -= 1;
+        self.is_built()
+    }
+
+    /// Damage the improvement from fighting on its tile.
+    ///
+    /// ## Returns
+    /// Whether the improvement is destroyed, i.e. the damage it took reached its `durability`.
+    pub fn damage(&mut self, damage: u8, durability: u8) -> bool {
+        self.lost_durability = self.lost_durability.saturating_add(damage);
+        self.lost_durability >= durability
+    }
+}
+
+/// The two tiles a tile connection lies between, starting with the tile owning the connection.
+/// Connections at the edge of the map don't connect to another tile and don't have this component.
 #[derive(Reflect, Component, Debug, Clone, PartialEq, Eq)]
 pub struct ConnectedTiles(pub TileEntity, pub TileEntity);
 
-#[derive(Component, Reflect, Debug, Clone, PartialEq, Eq)]
+/// The faction something like a unit belongs to.
+/// Tiles are not owned but controlled, see [crate::gameplay_plugin::resources::TileControl].
+#[derive(Component, Reflect, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Owner(pub FactionId);
+
+/// A unit on the tile at its [AxialCoordinates], belonging to the faction of its [Owner].
+#[derive(Component, Reflect, Default, Debug, Clone, PartialEq, Eq)]
 pub struct Unit {
+    unit_type: UnitTypePath,
+    unit_type_data: Handle<assets::UnitType>,
+    /// The health is the max health of the unit type minus this.
     lost_health: u8,
+    /// The organisation is the max organisation of the unit type minus this.
+    lost_organisation: u8,
+    /// The movement points spent this turn.
+    spent_movement_points: u8,
+    /// How well the unit has dug in. It grows every turn up to the max entrenchment of its tile type and is lost when the unit moves.
+    entrenchment: u8,
+    /// The experience gained in combat, which promotes the unit to the ranks of its unit type.
+    experience: u16,
 }
 
-#[derive(Copy, Component, Reflect, Debug, Clone, PartialEq, Eq)]
+impl Unit {
+    pub fn new(
+        unit_type: UnitTypePath,
+        unit_type_data: Handle<assets::UnitType>,
+        lost_health: u8,
+        lost_organisation: u8,
+        spent_movement_points: u8,
+        entrenchment: u8,
+        experience: u16,
+    ) -> Self {
+        Self {
+            unit_type,
+            unit_type_data,
+            lost_health,
+            lost_organisation,
+            spent_movement_points,
+            entrenchment,
+            experience,
+        }
+    }
+
+    /// The id the unit type is referenced by in save files.
+    pub fn unit_type(&self) -> &UnitTypePath {
+        &self.unit_type
+    }
+
+    pub fn unit_type_data(&self) -> &Handle<assets::UnitType> {
+        &self.unit_type_data
+    }
+
+    pub fn lost_health(&self) -> u8 {
+        self.lost_health
+    }
+
+    pub fn lost_organisation(&self) -> u8 {
+        self.lost_organisation
+    }
+
+    pub fn spent_movement_points(&self) -> u8 {
+        self.spent_movement_points
+    }
+
+    pub fn entrenchment(&self) -> u8 {
+        self.entrenchment
+    }
+
+    pub fn experience(&self) -> u16 {
+        self.experience
+    }
+
+    /// The rank of a unit of `unit_type`, if its experience reached any.
+    pub fn rank<'a>(&self, unit_type: &'a assets::UnitType) -> Option<&'a assets::Rank> {
+        unit_type.rank(self.experience)
+    }
+
+    pub fn has_ability(&self, unit_type: &assets::UnitType, ability: assets::Ability) -> bool {
+        unit_type.has_ability(self.experience, ability)
+    }
+
+    /// The attack damage of a unit of `unit_type`, multiplied by its rank.
+    pub fn attack_damage(&self, unit_type: &assets::UnitType) -> f32 {
+        f32::from(unit_type.attack_damage())
+            * self
+                .rank(unit_type)
+                .map_or(1.0, assets::Rank::attack_modifier)
+    }
+
+    /// The defense damage of a unit of `unit_type`, multiplied by its rank.
+    pub fn defense_damage(&self, unit_type: &assets::UnitType) -> f32 {
+        f32::from(unit_type.defense_damage())
+            * self
+                .rank(unit_type)
+                .map_or(1.0, assets::Rank::defense_modifier)
+    }
+
+    /// Gain `experience` as a unit of `unit_type`.
+    ///
+    /// ## Returns
+    /// The rank the unit is promoted to, if it reached a new one.
+    pub fn gain_experience<'a>(
+        &mut self,
+        experience: u16,
+        unit_type: &'a assets::UnitType,
+    ) -> Option<&'a assets::Rank> {
+        let previous_rank = self.rank(unit_type).map(assets::Rank::experience);
+        self.experience = self.experience.saturating_add(experience);
+        self.rank(unit_type)
+            .filter(|rank| Some(rank.experience()) != previous_rank)
+    }
+
+    pub fn health(&self, unit_type: &assets::UnitType) -> u8 {
+        unit_type.max_health().saturating_sub(self.lost_health)
+    }
+
+    pub fn organisation(&self, unit_type: &assets::UnitType) -> u8 {
+        unit_type
+            .max_organisation()
+            .saturating_sub(self.lost_organisation)
+    }
+
+    pub fn take_losses(&mut self, health: u8, organisation: u8) {
+        self.lost_health = self.lost_health.saturating_add(health);
+        self.lost_organisation = self.lost_organisation.saturating_add(organisation);
+    }
+
+    /// The movement points left this turn of a unit of `unit_type`.
+    pub fn remaining_movement_points(&self, unit_type: &assets::UnitType) -> u8 {
+        unit_type
+            .movement_points()
+            .saturating_sub(self.spent_movement_points)
+    }
+
+    pub fn spend_movement_points(&mut self, movement_points: u8) {
+        self.spent_movement_points = self.spent_movement_points.saturating_add(movement_points);
+    }
+
+    /// Regain all movement points at the start of a turn.
+    pub fn refresh_movement_points(&mut self) {
+        self.spent_movement_points = 0;
+    }
+
+    /// Dig in further by `levels` at the end of a turn, up to `max_entrenchment`.
+    /// The entrenchment drops to `max_entrenchment` if the unit is dug in deeper than its tile allows, e.g. after the tile changed its tile type.
+    /// Dig in further by `levels`, up to `max_entrenchment`.
+    /// Units which spent movement points this turn left their position, so they don't dig in.
+    pub fn entrench(&mut self, levels: u8, max_entrenchment: u8) {
+        if self.spent_movement_points > 0 {
+            return;
+        }
+        self.entrenchment = self
+            .entrenchment
+            .saturating_add(levels)
+            .min(max_entrenchment);
+    }
+
+    /// Lose all entrenchment, since the unit left its position.
+    pub fn leave_position(&mut self) {
+        self.entrenchment = 0;
+    }
+}
+
+/// A group of units which is ordered as one, like a division, belonging to the faction of its [Owner].
+/// Its units and subordinate formations of a lower [Echelon] are its children.
+#[derive(Component, Reflect, Debug, Clone, PartialEq, Eq)]
+pub struct Formation {
+    id: FormationId,
+    display_name: String,
+    echelon: Echelon,
+}
+
+impl Formation {
+    pub fn new(id: FormationId, display_name: String, echelon: Echelon) -> Self {
+        Self {
+            id,
+            display_name,
+            echelon,
+        }
+    }
+
+    /// The id the formation is referenced by in save files.
+    pub fn id(&self) -> &FormationId {
+        &self.id
+    }
+
+    pub fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    pub fn echelon(&self) -> Echelon {
+        self.echelon
+    }
+}
+
+/// The tiles a unit still moves through, starting with the next one.
+/// The unit moves as far as its movement points allow each turn and continues in the next.
+#[derive(Component, Reflect, Default, Debug, Clone, PartialEq, Eq)]
+pub struct MoveOrder(pub VecDeque<AxialCoordinates>);
+
+#[derive(Copy, Component, Reflect, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AxialCoordinates {
     q: i32,
     r: i32,
@@ -71,6 +441,19 @@ impl AxialCoordinates {
         (q.unsigned_abs() + r.unsigned_abs() + s.unsigned_abs()) / 2
     }
 
+    /// The six adjacent coordinates, starting with the right neighbour and going clockwise.
+    pub fn neighbours(&self) -> [AxialCoordinates; 6] {
+        let Self { q, r } = *self;
+        [
+            AxialCoordinates::new(q + 1, r),
+            AxialCoordinates::new(q, r + 1),
+            AxialCoordinates::new(q - 1, r + 1),
+            AxialCoordinates::new(q - 1, r),
+            AxialCoordinates::new(q, r - 1),
+            AxialCoordinates::new(q + 1, r - 1),
+        ]
+    }
+
     pub fn q(&self) -> i32 {
         self.q
     }
@@ -91,6 +474,17 @@ impl From<AxialCoordinates> for CubicCoordinates {
 const Q_VECTOR: Vec2 = Vec2 { x: 32.0, y: 0.0 };
 const R_VECTOR: Vec2 = Vec2 { x: 16.0, y: -21.0 };
 
+/// The corners of a tile relative to its center, starting with the upper right corner and going clockwise.
+/// The border to the neighbour `i` of [AxialCoordinates::neighbours()] lies between the corners `i` and `i + 1`.
+pub const TILE_CORNERS: [Vec2; 6] = [
+    Vec2::new(16.0, 7.0),
+    Vec2::new(16.0, -7.0),
+    Vec2::new(0.0, -14.0),
+    Vec2::new(-16.0, -7.0),
+    Vec2::new(-16.0, 7.0),
+    Vec2::new(0.0, 14.0),
+];
+
 // TODO!
 impl From<AxialCoordinates> for Vec2 {
     fn from(value: AxialCoordinates) -> Self {
@@ -101,6 +495,111 @@ impl From<AxialCoordinates> for Vec2 {
     }
 }
 
-// TODO!
-#[derive(Serialize, Deserialize, Component, Reflect, Debug, Clone, PartialEq, Eq)]
-pub struct TempConnectionComponent(pub usize);
+impl From<Vec2> for AxialCoordinates {
+    /// The coordinates of the tile containing the world position `value`.
+    fn from(value: Vec2) -> Self {
+        let r = value.y / R_VECTOR.y;
+        let q = (value.x - r * R_VECTOR.x) / Q_VECTOR.x;
+        let s = -q - r;
+        // Round to the nearest tile, keeping q + r + s = 0 by recalculating the component that was rounded the furthest.
+        let (mut rounded_q, mut rounded_r, rounded_s) = (q.round(), r.round(), s.round());
+        let (q_difference, r_difference, s_difference) = (
+            (rounded_q - q).abs(),
+            (rounded_r - r).abs(),
+            (rounded_s - s).abs(),
+        );
+        if q_difference > r_difference && q_difference > s_difference {
+            rounded_q = -rounded_r - rounded_s;
+        } else if r_difference > s_difference {
+            rounded_r = -rounded_q - rounded_s;
+        }
+        AxialCoordinates::new(rounded_q as i32, rounded_r as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests_axial_coordinates {
+    use bevy::math::Vec2;
+
+    use super::{AxialCoordinates, TILE_CORNERS};
+
+    #[test]
+    fn test_from_position() {
+        for q in -3..=3 {
+            for r in -3..=3 {
+                let coordinates = AxialCoordinates::new(q, r);
+                let center = Vec2::from(coordinates);
+                assert_eq!(AxialCoordinates::from(center), coordinates);
+                // Positions just inside of the corners still belong to the tile.
+                for corner in TILE_CORNERS {
+                    assert_eq!(AxialCoordinates::from(center + corner * 0.9), coordinates);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_improvement {
+    use bevy::asset::Handle;
+
+    use super::Improvement;
+
+    #[test]
+    fn test_build() {
+        let mut improvement = Improvement::new("fort".to_string(), Handle::default(), 2, 0);
+        assert!(!improvement.is_built());
+        assert!(!improvement.build());
+        assert!(improvement.build());
+        assert!(improvement.is_built());
+        // Building a built improvement does nothing.
+        assert!(!improvement.build());
+        assert_eq!(improvement.remaining_build_turns(), 0);
+    }
+
+    #[test]
+    fn test_damage() {
+        let mut improvement = Improvement::new("fort".to_string(), Handle::default(), 0, 0);
+        assert!(!improvement.damage(30, 60));
+        assert!(improvement.damage(30, 60));
+        assert!(improvement.damage(u8::MAX, 60));
+        assert_eq!(improvement.lost_durability(), u8::MAX);
+    }
+}
+
+#[cfg(test)]
+mod tests_terrain_work {
+    use super::TerrainWork;
+
+    #[test]
+    fn test_work() {
+        let mut terrain_work = TerrainWork::new("cleared".to_string(), 2);
+        assert!(!terrain_work.work());
+        assert!(terrain_work.work());
+        assert_eq!(terrain_work.remaining_turns(), 0);
+    }
+}
+
+#[cfg(test)]
+mod tests_unit {
+    use bevy::asset::Handle;
+
+    use super::Unit;
+
+    #[test]
+    fn test_entrench() {
+        let mut unit = Unit::new("infantry".to_string(), Handle::default(), 0, 0, 0, 0, 0);
+        unit.entrench(2, 3);
+        unit.entrench(2, 3);
+        assert_eq!(unit.entrenchment(), 3);
+
+        // A unit which moved this turn starts over and doesn't dig in until the next turn.
+        unit.spend_movement_points(1);
+        unit.leave_position();
+        unit.entrench(2, 3);
+        assert_eq!(unit.entrenchment(), 0);
+        unit.refresh_movement_points();
+        unit.entrench(2, 3);
+        assert_eq!(unit.entrenchment(), 2);
+    }
+}
@@ -0,0 +1,84 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+/// The directory (relative to `assets`) of the content shared by all scenarios.
+/// A scenario references library entries by their id (their directory name) and may override them by defining an entry with the same id itself.
+pub const LIBRARY_PATH: &str = "library";
+
+/// The kinds of content that can be defined in the library as well as in a scenario.
+#[derive(Copy, Debug, Clone, PartialEq, Eq)]
+pub enum ContentKind {
+    TileType,
+    UnitType,
+    ConnectionType,
+    ImprovementType,
+    WeatherType,
+}
+
+impl ContentKind {
+    /// The directory containing one subdirectory per entry.
+    pub fn directory(&self) -> &'static str {
+        match self {
+            ContentKind::TileType => "tile_types",
+            ContentKind::UnitType => "unit_types",
+            ContentKind::ConnectionType => "connection_types",
+            ContentKind::ImprovementType => "improvement_types",
+            ContentKind::WeatherType => "weather_types",
+        }
+    }
+
+    /// The file inside the directory of an entry defining it.
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            ContentKind::TileType => "tile_type.ron",
+            ContentKind::UnitType => "unit_type.ron",
+            ContentKind::ConnectionType => "connection_type.ron",
+            ContentKind::ImprovementType => "improvement_type.ron",
+            ContentKind::WeatherType => "weather_type.ron",
+        }
+    }
+
+    /// The path (relative to `assets`) of the entry `id` inside `root`, which is either a scenario or the library.
+    pub fn path(&self, root: &str, id: &str) -> String {
+        format!("{root}/{}/{id}/{}", self.directory(), self.file_name())
+    }
+}
+
+/// The path (relative to `assets`) of the entry `id` for the scenario at `scenario_path`.
+/// An entry defined by the scenario itself takes precedence over the library.
+pub fn resolve(scenario_path: &str, kind: ContentKind, id: &str) -> String {
+    let scenario_entry = kind.path(scenario_path, id);
+    if Path::new("assets").join(&scenario_entry).is_file() {
+        scenario_entry
+    } else {
+        kind.path(LIBRARY_PATH, id)
+    }
+}
+
+/// All entries available to the scenario at `scenario_path` by their id, with the ones defined by the scenario overriding the library.
+pub fn available(scenario_path: &str, kind: ContentKind) -> io::Result<HashMap<String, String>> {
+    let mut entries = HashMap::new();
+    for root in [LIBRARY_PATH, scenario_path] {
+        let directory = Path::new("assets").join(root).join(kind.directory());
+        let directory_entries = match fs::read_dir(directory) {
+            Ok(directory_entries) => directory_entries,
+            // Neither the library nor a scenario need to define any entries.
+            Err(err) if err.kind() == ErrorKind::NotFound => continue,
+            Err(err) => return Err(err),
+        };
+        for directory_entry in directory_entries {
+            let directory_entry = directory_entry?;
+            if !directory_entry.path().join(kind.file_name()).is_file() {
+                continue;
+            }
+            let id = directory_entry.file_name().to_string_lossy().into_owned();
+            let path = kind.path(root, &id);
+            entries.insert(id, path);
+        }
+    }
+    Ok(entries)
+}
@@ -0,0 +1,80 @@
+//! How the height of the terrain affects vision, combat, movement and rendering.
+
+use bevy::color::Color;
+
+use super::{components::Elevation, pathfinding::MovementCost};
+
+/// Every this many levels of elevation add one to the vision range of units on a tile.
+const LEVELS_PER_VISION_BONUS: u8 = 2;
+/// The share of damage lost per level the defender is above the attacker.
+const UPHILL_ATTACK_PENALTY_PER_LEVEL: f32 = 0.15;
+/// Attacking uphill never reduces the damage below this share.
+const MIN_UPHILL_ATTACK_MODIFIER: f32 = 0.4;
+/// Added to the movement cost per level climbed.
+const CLIMB_COST_PER_LEVEL: MovementCost = 1;
+/// The brightness of tiles at the lowest elevation. Every level is brighter up to full brightness.
+const LOWEST_BRIGHTNESS: f32 = 0.7;
+const BRIGHTNESS_PER_LEVEL: f32 = 0.1;
+
+/// Added to the vision range of units at `elevation`.
+pub fn vision_bonus(elevation: Elevation) -> u8 {
+    elevation.level() / LEVELS_PER_VISION_BONUS
+}
+
+/// Multiplies the damage of an attack from `attacker` onto `defender`.
+/// Only attacking uphill is penalized.
+pub fn attack_modifier(attacker: Elevation, defender: Elevation) -> f32 {
+    let climb = defender.level().saturating_sub(attacker.level());
+    (1.0 - f32::from(climb) * UPHILL_ATTACK_PENALTY_PER_LEVEL).max(MIN_UPHILL_ATTACK_MODIFIER)
+}
+
+/// Added to the movement cost of moving from `from` to `to`.
+/// Only climbing costs extra, descending is free.
+pub fn climb_cost(from: Elevation, to: Elevation) -> MovementCost {
+    MovementCost::from(to.level().saturating_sub(from.level())) * CLIMB_COST_PER_LEVEL
+}
+
+/// The tint of tiles at `elevation`, shading lower terrain darker.
+pub fn tint(elevation: Elevation) -> Color {
+    let brightness =
+        (LOWEST_BRIGHTNESS + f32::from(elevation.level()) * BRIGHTNESS_PER_LEVEL).min(1.0);
+    Color::srgb(brightness, brightness, brightness)
+}
+
+#[cfg(test)]
+mod tests_elevation {
+    use bevy::color::Color;
+
+    use super::{attack_modifier, climb_cost, tint, vision_bonus, Elevation};
+
+    #[test]
+    fn test_vision_bonus() {
+        assert_eq!(vision_bonus(Elevation::new(0)), 0);
+        assert_eq!(vision_bonus(Elevation::new(1)), 0);
+        assert_eq!(vision_bonus(Elevation::new(2)), 1);
+        assert_eq!(vision_bonus(Elevation::new(5)), 2);
+    }
+
+    #[test]
+    fn test_attack_modifier() {
+        assert_eq!(attack_modifier(Elevation::new(1), Elevation::new(1)), 1.0);
+        // Attacking downhill is not penalized.
+        assert_eq!(attack_modifier(Elevation::new(3), Elevation::new(0)), 1.0);
+        assert!((attack_modifier(Elevation::new(0), Elevation::new(2)) - 0.7).abs() < 1e-6);
+        assert_eq!(attack_modifier(Elevation::new(0), Elevation::new(10)), 0.4);
+    }
+
+    #[test]
+    fn test_climb_cost() {
+        assert_eq!(climb_cost(Elevation::new(0), Elevation::new(2)), 2);
+        assert_eq!(climb_cost(Elevation::new(2), Elevation::new(0)), 0);
+        assert_eq!(climb_cost(Elevation::new(1), Elevation::new(1)), 0);
+    }
+
+    #[test]
+    fn test_tint() {
+        assert_eq!(tint(Elevation::new(4)), Color::srgb(1.0, 1.0, 1.0));
+        assert_eq!(tint(Elevation::new(u8::MAX)), Color::srgb(1.0, 1.0, 1.0));
+        assert_ne!(tint(Elevation::new(0)), Color::srgb(1.0, 1.0, 1.0));
+    }
+}
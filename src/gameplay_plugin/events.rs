@@ -0,0 +1,87 @@
+use bevy::{
+    prelude::{Entity, Event},
+    reflect::Reflect,
+};
+
+use super::{
+    components::AxialCoordinates,
+    save_file::{FactionId, ImprovementTypePath, TileTypePath},
+};
+
+/// A unit of `faction` entered the tile at `coordinates`.
+#[derive(Event, Reflect, Debug, Clone, PartialEq, Eq)]
+pub struct TileEntered {
+    pub coordinates: AxialCoordinates,
+    pub faction: FactionId,
+}
+
+/// The tile at `coordinates` changed its controller.
+#[derive(Event, Reflect, Debug, Clone, PartialEq, Eq)]
+pub struct TileCaptured {
+    pub coordinates: AxialCoordinates,
+    pub previous_controller: Option<FactionId>,
+    pub controller: FactionId,
+}
+
+/// The turn `turn` ended and the next one begins.
+#[derive(Event, Reflect, Debug, Clone, PartialEq, Eq)]
+pub struct TurnEnded {
+    pub turn: u32,
+}
+
+/// Order the unit `unit` to move to `destination` along the cheapest path.
+#[derive(Event, Reflect, Debug, Clone, PartialEq, Eq)]
+pub struct MoveUnit {
+    pub unit: Entity,
+    pub destination: AxialCoordinates,
+}
+
+/// Order the `attackers` to attack the units on the adjacent tile at `target`.
+#[derive(Event, Reflect, Debug, Clone, PartialEq, Eq)]
+pub struct Attack {
+    pub attackers: Vec<Entity>,
+    pub target: AxialCoordinates,
+}
+
+/// Start building an improvement on `target`, which is either a tile or a tile connection.
+/// `faction` pays for it with its production.
+#[derive(Event, Reflect, Debug, Clone, PartialEq, Eq)]
+pub struct BuildImprovement {
+    pub target: Entity,
+    pub improvement_type: ImprovementTypePath,
+    pub faction: FactionId,
+}
+
+/// Units fought over the tile at `coordinates`, attacking from `attacker_coordinates`.
+#[derive(Event, Reflect, Debug, Clone, PartialEq, Eq)]
+pub struct TileFoughtOver {
+    pub coordinates: AxialCoordinates,
+    pub attacker_coordinates: AxialCoordinates,
+    /// How much damage the fighting did to the tile, its improvements and the improvements on the connection it was attacked across.
+    pub damage: u8,
+}
+
+/// Something happened to the terrain of the tile at `coordinates`, e.g. `cleared` for a forest being cut down.
+/// Whether and how the tile changes is defined by the transitions of its tile type.
+#[derive(Event, Reflect, Debug, Clone, PartialEq, Eq)]
+pub struct TerrainEvent {
+    pub coordinates: AxialCoordinates,
+    pub name: String,
+}
+
+/// Start the work for the terrain event `event` on the tile at `coordinates`, as defined by the transitions of its tile type.
+/// `faction` pays for it with its production and those of its `units` on the tile spend their remaining movement points on it.
+#[derive(Event, Reflect, Debug, Clone, PartialEq, Eq)]
+pub struct StartTerrainWork {
+    pub coordinates: AxialCoordinates,
+    pub event: String,
+    pub faction: FactionId,
+    pub units: Vec<Entity>,
+}
+
+/// Turn the tile at `coordinates` into a tile of the type `tile_type`.
+#[derive(Event, Reflect, Debug, Clone, PartialEq, Eq)]
+pub struct ChangeTileType {
+    pub coordinates: AxialCoordinates,
+    pub tile_type: TileTypePath,
+}
@@ -0,0 +1,21 @@
+//! How improvements on tiles and tile connections are drawn.
+
+use bevy::{
+    color::{Alpha, Color},
+    math::Vec3,
+};
+
+use super::components::Improvement;
+
+/// Improvements are drawn relative to their tile or tile connection, above connection overlays.
+pub const OFFSET: Vec3 = Vec3::new(0.0, 0.0, 1.0);
+/// Improvements under construction are drawn translucent.
+const UNDER_CONSTRUCTION_ALPHA: f32 = 0.4;
+
+pub fn tint(improvement: &Improvement) -> Color {
+    if improvement.is_built() {
+        Color::WHITE
+    } else {
+        Color::WHITE.with_alpha(UNDER_CONSTRUCTION_ALPHA)
+    }
+}
@@ -1,4 +1,4 @@
 use bevy::{ecs::component::Component, reflect::Reflect};
 
 #[derive(Component, Reflect, Debug, Clone, PartialEq, Eq)]
-pub(super) struct MainCamera;
+pub(in crate::gameplay_plugin::in_game_plugin) struct MainCamera;
@@ -4,7 +4,6 @@ use bevy::{
     prelude::{OnEnter, ReflectComponent},
     state::condition::in_state,
 };
-use components::MainCamera;
 
 use crate::gameplay_plugin::GameplayStates;
 
@@ -13,6 +12,8 @@ use self::systems::{camera_controller, setup};
 mod components;
 mod systems;
 
+pub(super) use self::components::MainCamera;
+
 pub(super) struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
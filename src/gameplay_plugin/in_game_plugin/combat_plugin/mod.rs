@@ -0,0 +1,24 @@
+use bevy::{
+    app::{Plugin, Update},
+    prelude::{in_state, IntoSystemConfigs},
+};
+
+use crate::gameplay_plugin::GameplayStates;
+
+use self::systems::resolve_attacks;
+
+mod systems;
+
+pub(super) use self::systems::CombatRules;
+
+/// Resolves attacks of units onto adjacent tiles and makes beaten units retreat.
+pub(super) struct CombatPlugin;
+
+impl Plugin for CombatPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(
+            Update,
+            resolve_attacks.run_if(in_state(GameplayStates::InGame)),
+        );
+    }
+}
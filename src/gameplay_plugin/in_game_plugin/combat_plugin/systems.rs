@@ -0,0 +1,429 @@
+use std::cmp::Reverse;
+
+use bevy::{
+    asset::Assets,
+    ecs::system::SystemParam,
+    hierarchy::DespawnRecursiveExt,
+    log::warn,
+    prelude::{Commands, Entity, EventReader, EventWriter, Query, Res, ResMut},
+    transform::components::Transform,
+};
+
+use crate::{
+    gameplay_plugin::{
+        assets::{self, MovementClass},
+        combat::{self, CombatPreview, CombatResult, Combatant, Rolls},
+        components::{AxialCoordinates, Improvement, MoveOrder, Owner, TileType, Unit},
+        elevation,
+        events::{Attack, TileFoughtOver},
+        in_game_plugin::movement_plugin::MovementRules,
+        movement,
+        random::RngStream,
+        resources::{Faction, Factions, GameRng, Weather, WeatherTypes},
+        save_file::FactionId,
+        units, weather,
+    },
+    toast_plugin::Toasts,
+};
+
+/// The units taking part in an attack onto a tile and the properties of the tile.
+pub(in crate::gameplay_plugin::in_game_plugin) struct Engagement {
+    /// The attackers in the order they were ordered to attack in.
+    pub attacking: Vec<(Entity, Combatant)>,
+    /// The defenders, fresh units first.
+    pub defending: Vec<(Entity, Combatant)>,
+    pub combat_width: u8,
+    pub defense_modifier: f32,
+}
+
+impl Engagement {
+    fn attackers(&self) -> Vec<Combatant> {
+        self.attacking
+            .iter()
+            .map(|(_, attacker)| attacker.clone())
+            .collect()
+    }
+
+    fn defenders(&self) -> Vec<Combatant> {
+        self.defending
+            .iter()
+            .map(|(_, defender)| defender.clone())
+            .collect()
+    }
+
+    /// The attackers and defenders taking part in the combat, see [combat::participants].
+    fn participants(&self) -> Vec<Entity> {
+        let attackers = combat::participants(&self.attackers(), self.combat_width);
+        let defenders = combat::participants(&self.defenders(), self.combat_width);
+        self.attacking
+            .iter()
+            .take(attackers)
+            .chain(self.defending.iter().take(defenders))
+            .map(|(unit, _)| *unit)
+            .collect()
+    }
+
+    pub fn resolve(&self, rolls: Rolls) -> CombatResult {
+        combat::resolve(
+            &self.attackers(),
+            &self.defenders(),
+            self.combat_width,
+            self.defense_modifier,
+            rolls,
+        )
+    }
+
+    pub fn preview(&self) -> CombatPreview {
+        combat::preview(
+            &self.attackers(),
+            &self.defenders(),
+            self.combat_width,
+            self.defense_modifier,
+        )
+    }
+}
+
+/// Looks up how units, the tile fought over, the improvements on it, its weather and the terrain between the attackers and the tile affect combat.
+#[derive(SystemParam)]
+pub(in crate::gameplay_plugin::in_game_plugin) struct CombatRules<'w, 's> {
+    movement_rules: MovementRules<'w, 's>,
+    unit_types: Res<'w, Assets<assets::UnitType>>,
+    tiles: Query<'w, 's, &'static TileType>,
+    tile_types: Res<'w, Assets<assets::TileType>>,
+    improvements: Query<'w, 's, &'static Improvement>,
+    improvement_types: Res<'w, Assets<assets::ImprovementType>>,
+    weather: Res<'w, Weather>,
+    weather_types: Res<'w, WeatherTypes>,
+    weather_type_assets: Res<'w, Assets<assets::WeatherType>>,
+}
+
+impl CombatRules<'_, '_> {
+    pub fn movement_rules(&self) -> &MovementRules<'_, '_> {
+        &self.movement_rules
+    }
+
+    fn tile_type(&self, coordinates: AxialCoordinates) -> Option<&assets::TileType> {
+        let tile = self.movement_rules.hexagonal_map().get(coordinates)?;
+        let tile_type = self.tiles.get(tile.tile_entity()).ok()?;
+        self.tile_types.get(tile_type.tile_type_data())
+    }
+
+    /// The combat width of the tile at `coordinates` in its current weather.
+    pub fn combat_width(&self, coordinates: AxialCoordinates) -> Option<u8> {
+        Some(weather::combat_width(
+            self.tile_type(coordinates)?.combat_width(),
+            self.weather.weather_type_at(
+                coordinates,
+                &self.weather_types,
+                &self.weather_type_assets,
+            ),
+        ))
+    }
+
+    /// The defense modifier of the tile type of the tile at `coordinates` multiplied by the ones of the built improvements on it.
+    pub fn defense_modifier(&self, coordinates: AxialCoordinates) -> Option<f32> {
+        let tile = self.movement_rules.hexagonal_map().get(coordinates)?;
+        let improvements = self
+            .movement_rules
+            .children(tile.tile_entity())
+            .filter_map(|child| self.improvements.get(child).ok())
+            .filter(|improvement| improvement.is_built())
+            .filter_map(|improvement| {
+                self.improvement_types
+                    .get(improvement.improvement_type_data())
+            })
+            .map(assets::ImprovementType::defense_modifier)
+            .product::<f32>();
+        Some(self.tile_type(coordinates)?.defense_modifier() * improvements)
+    }
+
+    /// Multiplies the damage of an attack from `from` onto the adjacent tile `to`.
+    /// Attacking uphill and across connections like rivers is penalized, unless an improvement like a bridge negates the connection.
+    pub fn attack_modifier(&self, from: AxialCoordinates, to: AxialCoordinates) -> f32 {
+        let elevation_modifier = self
+            .movement_rules
+            .elevation(from)
+            .zip(self.movement_rules.elevation(to))
+            .map_or(1.0, |(from, to)| elevation::attack_modifier(from, to));
+        let connection_modifier = 1.0
+            - self
+                .movement_rules
+                .connection_type_between(from, to)
+                .map_or(0.0, assets::ConnectionType::combat_penalty);
+        elevation_modifier * connection_modifier
+    }
+
+    /// The engagement of the `attackers` attacking the tile at `target`.
+    /// `units` are all units with their coordinates and owners. Attackers which are not adjacent enemies of the defenders or have no movement points left are left out.
+    ///
+    /// ## Returns
+    /// - None: There are no defenders on the tile or none of the attackers can attack it.
+    pub fn engagement(
+        &self,
+        attackers: &[Entity],
+        target: AxialCoordinates,
+        units: &[(Entity, &Unit, AxialCoordinates, &FactionId)],
+    ) -> Option<Engagement> {
+        let combat_width = self.combat_width(target)?;
+        let defense_modifier = self.defense_modifier(target)?;
+        let (_, _, _, defending_faction) = units
+            .iter()
+            .find(|(_, _, coordinates, _)| *coordinates == target)?;
+
+        let mut defending: Vec<(Entity, Combatant)> = units
+            .iter()
+            .filter(|(_, _, coordinates, owner)| {
+                *coordinates == target && owner == defending_faction
+            })
+            .filter_map(|(defender, unit, ..)| {
+                let unit_type = self.unit_types.get(unit.unit_type_data())?;
+                let damage = unit.defense_damage(unit_type);
+                Some((
+                    *defender,
+                    combatant(unit, unit_type, damage, unit.entrenchment()),
+                ))
+            })
+            .collect();
+        defending.sort_by_key(|(_, defender)| Reverse(defender.organisation));
+        let attacking: Vec<(Entity, Combatant)> = attackers
+            .iter()
+            .filter_map(|attacker| {
+                let (_, unit, coordinates, owner) =
+                    units.iter().find(|(unit, ..)| unit == attacker)?;
+                let unit_type = self.unit_types.get(unit.unit_type_data())?;
+                if owner == defending_faction
+                    || !coordinates.neighbours().contains(&target)
+                    || unit.remaining_movement_points(unit_type) == 0
+                {
+                    return None;
+                }
+                let damage =
+                    unit.attack_damage(unit_type) * self.attack_modifier(*coordinates, target);
+                Some((*attacker, combatant(unit, unit_type, damage, 0)))
+            })
+            .collect();
+        if attacking.is_empty() || defending.is_empty() {
+            return None;
+        }
+        Some(Engagement {
+            attacking,
+            defending,
+            combat_width,
+            defense_modifier,
+        })
+    }
+}
+
+/// Resolve every ordered attack.
+/// Attacking costs all remaining movement points, so every unit attacks at most once per turn.
+/// Units that fought gain experience and may be promoted.
+/// Units without health left are destroyed, fighting units without organisation left retreat to an adjacent tile or are destroyed if there is none.
+pub(super) fn resolve_attacks(
+    mut commands: Commands,
+    mut attack_events: EventReader<Attack>,
+    mut units: Query<(
+        Entity,
+        &mut Unit,
+        &mut AxialCoordinates,
+        &mut Transform,
+        &Owner,
+    )>,
+    unit_types: Res<Assets<assets::UnitType>>,
+    factions: Res<Factions>,
+    combat_rules: CombatRules,
+    mut game_rng: ResMut<GameRng>,
+    mut tile_fought_over_events: EventWriter<TileFoughtOver>,
+    mut toasts: ResMut<Toasts>,
+) {
+    for Attack { attackers, target } in attack_events.read() {
+        let positions: Vec<_> = units
+            .iter()
+            .map(|(unit, unit_data, coordinates, _, Owner(owner))| {
+                (unit, unit_data, *coordinates, owner)
+            })
+            .collect();
+        let Some(engagement) = combat_rules.engagement(attackers, *target, &positions) else {
+            warn!("None of {attackers:?} can attack {target:?}");
+            continue;
+        };
+        let participants = engagement.participants();
+        let result = engagement.resolve(Rolls {
+            attack: game_rng.next_f32(RngStream::Combat),
+            defense: game_rng.next_f32(RngStream::Combat),
+        });
+        let Engagement {
+            attacking,
+            defending,
+            ..
+        } = engagement;
+
+        let mut attacker_coordinates: Vec<AxialCoordinates> = Vec::new();
+        let mut promotions: Vec<(Entity, String)> = Vec::new();
+        for (((attacker, _), losses), experience) in attacking
+            .iter()
+            .zip(&result.attacker_losses)
+            .zip(&result.attacker_experience)
+        {
+            let Ok((_, mut unit, coordinates, ..)) = units.get_mut(*attacker) else {
+                continue;
+            };
+            unit.take_losses(losses.health, losses.organisation);
+            if let Some(rank) = unit_types
+                .get(unit.unit_type_data())
+                .and_then(|unit_type| unit.gain_experience(*experience, unit_type))
+            {
+                promotions.push((*attacker, rank.display_name().to_string()));
+            }
+            unit.spend_movement_points(u8::MAX);
+            if !attacker_coordinates.contains(&*coordinates) {
+                attacker_coordinates.push(*coordinates);
+            }
+        }
+        for (((defender, _), losses), experience) in defending
+            .iter()
+            .zip(&result.defender_losses)
+            .zip(&result.defender_experience)
+        {
+            if let Ok((_, mut unit, ..)) = units.get_mut(*defender) {
+                unit.take_losses(losses.health, losses.organisation);
+                if let Some(rank) = unit_types
+                    .get(unit.unit_type_data())
+                    .and_then(|unit_type| unit.gain_experience(*experience, unit_type))
+                {
+                    promotions.push((*defender, rank.display_name().to_string()));
+                }
+            }
+        }
+        // The damage to the tile is shared by the tiles it was attacked from, so the connections they attacked across are damaged too.
+        let damage = result
+            .tile_damage()
+            .div_ceil(attacker_coordinates.len() as u8);
+        for attacker_coordinates in attacker_coordinates {
+            tile_fought_over_events.send(TileFoughtOver {
+                coordinates: *target,
+                attacker_coordinates,
+                damage,
+            });
+        }
+
+        let mut positions: Vec<(Entity, AxialCoordinates, FactionId)> = units
+            .iter()
+            .map(|(unit, _, coordinates, _, Owner(owner))| (unit, *coordinates, owner.clone()))
+            .collect();
+        // Units which didn't fight can't be forced to retreat, even if they were out of organisation before.
+        for unit in &participants {
+            let Ok((_, mut unit_data, mut coordinates, mut transform, Owner(faction))) =
+                units.get_mut(*unit)
+            else {
+                continue;
+            };
+            let Some(unit_type) = unit_types.get(unit_data.unit_type_data()) else {
+                continue;
+            };
+            let description = format!(
+                "{} {}",
+                factions
+                    .get(faction)
+                    .map_or(faction.as_str(), Faction::display_name),
+                unit_type.display_name()
+            );
+            if unit_data.health(unit_type) == 0 {
+                toasts.info(format!("{description} was destroyed"));
+                commands.entity(*unit).despawn_recursive();
+                positions.retain(|(other, ..)| other != unit);
+                continue;
+            }
+            if let Some((_, rank)) = promotions.iter().find(|(promoted, _)| promoted == unit) {
+                toasts.info(format!("{description} was promoted to {rank}"));
+            }
+            if unit_data.organisation(unit_type) > 0 {
+                continue;
+            }
+            match retreat_destination(
+                *coordinates,
+                faction,
+                unit_type.movement_class(),
+                &positions,
+                combat_rules.movement_rules(),
+            ) {
+                Some(destination) => {
+                    toasts.info(format!("{description} retreated"));
+                    unit_data.spend_movement_points(u8::MAX);
+                    unit_data.leave_position();
+                    *coordinates = destination;
+                    *transform = units::transform(destination);
+                    commands.entity(*unit).remove::<MoveOrder>();
+                    if let Some((_, position, _)) =
+                        positions.iter_mut().find(|(other, ..)| other == unit)
+                    {
+                        *position = destination;
+                    }
+                }
+                None => {
+                    toasts.info(format!(
+                        "{description} was destroyed, as it could not retreat"
+                    ));
+                    commands.entity(*unit).despawn_recursive();
+                    positions.retain(|(other, ..)| other != unit);
+                }
+            }
+        }
+    }
+}
+
+fn combatant(
+    unit: &Unit,
+    unit_type: &assets::UnitType,
+    damage: f32,
+    entrenchment: u8,
+) -> Combatant {
+    Combatant {
+        damage,
+        combat_width: unit_type.combat_width(),
+        health: unit.health(unit_type),
+        max_health: unit_type.max_health(),
+        organisation: unit.organisation(unit_type),
+        entrenchment,
+    }
+}
+
+/// The adjacent tile a unit of `faction` and `movement_class` at `coordinates` retreats to.
+/// It has to be able to enter the tile, no enemy may stand on it and its stack must have room for it. Tiles outside of enemy zones of control are preferred, then cheaper ones.
+fn retreat_destination(
+    coordinates: AxialCoordinates,
+    faction: &FactionId,
+    movement_class: MovementClass,
+    positions: &[(Entity, AxialCoordinates, FactionId)],
+    movement_rules: &MovementRules,
+) -> Option<AxialCoordinates> {
+    coordinates
+        .neighbours()
+        .into_iter()
+        .filter(|neighbour| {
+            !positions
+                .iter()
+                .any(|(_, position, owner)| position == neighbour && owner != faction)
+        })
+        .filter(|neighbour| {
+            movement::room_on_tile(
+                *neighbour,
+                faction,
+                positions
+                    .iter()
+                    .map(|(_, position, owner)| (*position, owner)),
+            ) > 0
+        })
+        .filter_map(|neighbour| {
+            let step_cost = movement_rules.step_cost(movement_class, coordinates, neighbour)?;
+            let in_enemy_zone_of_control = movement_rules.in_enemy_zone_of_control(
+                neighbour,
+                faction,
+                positions
+                    .iter()
+                    .map(|(_, position, owner)| (*position, owner)),
+            );
+            Some((neighbour, (in_enemy_zone_of_control, step_cost)))
+        })
+        .min_by_key(|(_, preference)| *preference)
+        .map(|(neighbour, _)| neighbour)
+}
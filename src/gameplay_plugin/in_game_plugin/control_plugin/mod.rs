@@ -0,0 +1,22 @@
+use bevy::{
+    app::{Plugin, Update},
+    prelude::{in_state, IntoSystemConfigs},
+};
+
+use crate::gameplay_plugin::GameplayStates;
+
+use self::systems::{capture_tiles, draw_control_borders};
+
+mod systems;
+
+/// Keeps track of which faction controls which tile and draws the borders between them.
+pub(super) struct ControlPlugin;
+
+impl Plugin for ControlPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(
+            Update,
+            (capture_tiles, draw_control_borders).run_if(in_state(GameplayStates::InGame)),
+        );
+    }
+}
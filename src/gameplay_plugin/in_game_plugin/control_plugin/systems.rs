@@ -0,0 +1,56 @@
+use bevy::{
+    math::Vec2,
+    prelude::{EventReader, EventWriter, Gizmos, Query, Res, ResMut, Without},
+};
+
+use crate::gameplay_plugin::{
+    components::{AxialCoordinates, Owner, TileType, TILE_CORNERS},
+    events::{TileCaptured, TileEntered},
+    resources::{Factions, TileControl},
+};
+
+/// How far the borders are drawn inside of the tiles, so the borders of both factions are visible where they meet.
+const BORDER_INSET: f32 = 0.9;
+
+pub(super) fn capture_tiles(
+    mut tile_entered_events: EventReader<TileEntered>,
+    mut tile_captured_events: EventWriter<TileCaptured>,
+    mut tile_control: ResMut<TileControl>,
+    owners: Query<(&AxialCoordinates, &Owner), Without<TileType>>,
+) {
+    for TileEntered {
+        coordinates,
+        faction,
+    } in tile_entered_events.read()
+    {
+        let present_factions = owners
+            .iter()
+            .filter(|(owner_coordinates, _)| *owner_coordinates == coordinates)
+            .map(|(_, Owner(owner))| owner);
+        if let Some(tile_captured) = tile_control.capture(*coordinates, faction, present_factions) {
+            tile_captured_events.send(tile_captured);
+        }
+    }
+}
+
+/// Draw a border in the color of the controlling faction wherever a controlled tile borders a tile it does not control.
+pub(super) fn draw_control_borders(
+    mut gizmos: Gizmos,
+    tile_control: Res<TileControl>,
+    factions: Res<Factions>,
+) {
+    for (controller, coordinates) in tile_control.0.iter() {
+        let Some(faction) = controller.as_ref().and_then(|id| factions.get(id)) else {
+            continue;
+        };
+        let center = Vec2::from(coordinates);
+        for (index, neighbour) in coordinates.neighbours().into_iter().enumerate() {
+            if tile_control.controller(neighbour) == controller.as_ref() {
+                continue;
+            }
+            let start = center + TILE_CORNERS[index] * BORDER_INSET;
+            let end = center + TILE_CORNERS[(index + 1) % TILE_CORNERS.len()] * BORDER_INSET;
+            gizmos.line_2d(start, end, faction.color());
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use bevy::{
+    app::{Plugin, Update},
+    prelude::{in_state, IntoSystemConfigs},
+};
+
+use crate::gameplay_plugin::GameplayStates;
+
+use super::movement_plugin::refresh_movement_points;
+
+use self::systems::{draw_entrenchment, entrench};
+
+mod systems;
+
+/// Lets units that stay on their tile dig in and shows how well they are entrenched on their counter.
+pub(super) struct EntrenchmentPlugin;
+
+impl Plugin for EntrenchmentPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(
+            Update,
+            // The movement points spent during the turn tell which units left their position.
+            (entrench.before(refresh_movement_points), draw_entrenchment)
+                .run_if(in_state(GameplayStates::InGame)),
+        );
+    }
+}
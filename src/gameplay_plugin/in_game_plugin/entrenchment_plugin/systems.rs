@@ -0,0 +1,67 @@
+use bevy::{
+    asset::Assets,
+    color::Color,
+    math::Vec2,
+    prelude::{EventReader, Gizmos, Query, Res, Without},
+};
+
+use crate::gameplay_plugin::{
+    assets,
+    components::{AxialCoordinates, MoveOrder, TileType, Unit},
+    events::TurnEnded,
+    resources::{HexagonalMap, Tile},
+};
+
+/// Each level of entrenchment is drawn as a dash below the unit counter.
+const MARK_OFFSET: f32 = -11.0;
+const MARK_SPACING: f32 = 4.0;
+const MARK_WIDTH: f32 = 2.5;
+const MARK_COLOR: Color = Color::BLACK;
+
+/// Let every unit which stayed on its tile dig in further at the end of a turn, up to the max entrenchment of the tile type of its tile.
+/// Units that moved or attacked during the turn or are still on their way lost their entrenchment and don't dig in. Units with the [assets::Ability::DigIn] dig in faster.
+pub(super) fn entrench(
+    mut turn_ended_events: EventReader<TurnEnded>,
+    mut units: Query<(&mut Unit, &AxialCoordinates), Without<MoveOrder>>,
+    unit_types: Res<Assets<assets::UnitType>>,
+    hexagonal_map: Res<HexagonalMap<Tile>>,
+    tiles: Query<&TileType>,
+    tile_types: Res<Assets<assets::TileType>>,
+) {
+    for _ in turn_ended_events.read() {
+        for (mut unit, coordinates) in &mut units {
+            let Some(tile_type) = hexagonal_map
+                .get(*coordinates)
+                .and_then(|tile| tiles.get(tile.tile_entity()).ok())
+                .and_then(|tile_type| tile_types.get(tile_type.tile_type_data()))
+            else {
+                continue;
+            };
+            let levels = match unit_types.get(unit.unit_type_data()) {
+                Some(unit_type) if unit.has_ability(unit_type, assets::Ability::DigIn) => 2,
+                _ => 1,
+            };
+            unit.entrench(levels, tile_type.max_entrenchment());
+        }
+    }
+}
+
+/// Draw the entrenchment of every unit below its counter.
+pub(super) fn draw_entrenchment(mut gizmos: Gizmos, units: Query<(&Unit, &AxialCoordinates)>) {
+    for (unit, coordinates) in &units {
+        let marks = unit.entrenchment();
+        if marks == 0 {
+            continue;
+        }
+        let first = Vec2::from(*coordinates)
+            + Vec2::new(-MARK_SPACING * f32::from(marks - 1) / 2.0, MARK_OFFSET);
+        for mark in 0..marks {
+            let center = first + Vec2::X * MARK_SPACING * f32::from(mark);
+            gizmos.line_2d(
+                center - Vec2::X * MARK_WIDTH / 2.0,
+                center + Vec2::X * MARK_WIDTH / 2.0,
+                MARK_COLOR,
+            );
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use bevy::{
+    app::{Plugin, Update},
+    prelude::{in_state, IntoSystemConfigs},
+    render::texture::Image,
+};
+
+use crate::gameplay_plugin::{
+    assets,
+    components::{ConnectionType, Improvement, Unit},
+    GameplayStates,
+};
+
+use self::systems::{notify_reloaded, reload, reload_tile_types};
+
+mod systems;
+
+/// Applies changes to the definitions of tile, connection, improvement, unit and weather types while playing.
+/// Assets are only reloaded if the `hot_reload` feature is enabled, which watches the asset files for changes.
+pub(super) struct HotReloadPlugin;
+
+impl Plugin for HotReloadPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(
+            Update,
+            (
+                reload_tile_types,
+                reload::<assets::ConnectionType, ConnectionType>,
+                reload::<assets::ImprovementType, Improvement>,
+                reload::<assets::UnitType, Unit>,
+                notify_reloaded::<assets::TileType>,
+                notify_reloaded::<assets::ConnectionType>,
+                notify_reloaded::<assets::ImprovementType>,
+                notify_reloaded::<assets::UnitType>,
+                notify_reloaded::<assets::WeatherType>,
+                notify_reloaded::<Image>,
+            )
+                .run_if(in_state(GameplayStates::InGame)),
+        );
+    }
+}
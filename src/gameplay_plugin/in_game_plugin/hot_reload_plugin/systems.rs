@@ -0,0 +1,146 @@
+use std::collections::HashSet;
+
+use bevy::{
+    asset::{Asset, AssetEvent, AssetLoadFailedEvent, AssetServer, Assets, Handle},
+    prelude::{Component, EventReader, Query, Res, ResMut},
+    render::texture::Image,
+};
+
+use crate::{
+    gameplay_plugin::{
+        assets,
+        components::{AxialCoordinates, ConnectionType, Improvement, TileType, Unit},
+        in_game_plugin::systems::tile_movement_costs,
+        resources::{HexagonalMap, Tile, TileMovementCosts, Weather, WeatherTypes},
+    },
+    toast_plugin::Toasts,
+};
+
+/// Update the cached movement costs when a tile or weather type is modified.
+/// The sprites of the tiles are updated by the tile sprite plugin and the weather overlays by the weather plugin.
+pub(super) fn reload_tile_types(
+    mut tile_type_events: EventReader<AssetEvent<assets::TileType>>,
+    mut weather_type_events: EventReader<AssetEvent<assets::WeatherType>>,
+    tiles: Query<(&TileType, &AxialCoordinates)>,
+    tile_types: Res<Assets<assets::TileType>>,
+    hexagonal_map: Res<HexagonalMap<Tile>>,
+    weather: Res<Weather>,
+    weather_types: Res<WeatherTypes>,
+    weather_type_assets: Res<Assets<assets::WeatherType>>,
+    mut tile_movement_costs_cache: ResMut<TileMovementCosts>,
+) {
+    let tile_types_modified = tile_type_events
+        .read()
+        .any(|event| matches!(event, AssetEvent::Modified { .. }));
+    let weather_types_modified = weather_type_events
+        .read()
+        .any(|event| matches!(event, AssetEvent::Modified { .. }));
+    if !tile_types_modified && !weather_types_modified {
+        return;
+    }
+
+    *tile_movement_costs_cache = tile_movement_costs(
+        hexagonal_map.radius(),
+        tiles
+            .iter()
+            .map(|(tile_type, coordinates)| (tile_type, *coordinates)),
+        &tile_types,
+        &weather,
+        &weather_types,
+        &weather_type_assets,
+    );
+}
+
+/// A component of entities drawn with the texture of the asset they reference, like a unit and its unit type.
+pub(super) trait Textured {
+    type Asset: Asset;
+
+    fn asset(&self) -> &Handle<Self::Asset>;
+
+    fn texture(asset: &Self::Asset) -> &Handle<Image>;
+}
+
+impl Textured for ConnectionType {
+    type Asset = assets::ConnectionType;
+
+    fn asset(&self) -> &Handle<Self::Asset> {
+        self.connection_type_data()
+    }
+
+    fn texture(asset: &Self::Asset) -> &Handle<Image> {
+        asset.texture()
+    }
+}
+
+impl Textured for Improvement {
+    type Asset = assets::ImprovementType;
+
+    fn asset(&self) -> &Handle<Self::Asset> {
+        self.improvement_type_data()
+    }
+
+    fn texture(asset: &Self::Asset) -> &Handle<Image> {
+        asset.texture()
+    }
+}
+
+impl Textured for Unit {
+    type Asset = assets::UnitType;
+
+    fn asset(&self) -> &Handle<Self::Asset> {
+        self.unit_type_data()
+    }
+
+    fn texture(asset: &Self::Asset) -> &Handle<Image> {
+        asset.texture()
+    }
+}
+
+/// Apply the new texture of every modified asset of type `A` to the entities whose component `C` references it.
+pub(super) fn reload<A: Asset, C: Component + Textured<Asset = A>>(
+    mut asset_events: EventReader<AssetEvent<A>>,
+    mut entities: Query<(&C, &mut Handle<Image>)>,
+    assets: Res<Assets<A>>,
+) {
+    let modified: HashSet<_> = asset_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    if modified.is_empty() {
+        return;
+    }
+
+    for (component, mut texture) in &mut entities {
+        if !modified.contains(&component.asset().id()) {
+            continue;
+        }
+        if let Some(asset) = assets.get(component.asset()) {
+            *texture = C::texture(asset).clone();
+        }
+    }
+}
+
+/// Show a toast for every asset file that was reloaded or could not be reloaded.
+/// Assets without a path, like images created at runtime, are ignored.
+pub(super) fn notify_reloaded<A: Asset>(
+    mut asset_events: EventReader<AssetEvent<A>>,
+    mut failed_events: EventReader<AssetLoadFailedEvent<A>>,
+    asset_server: Res<AssetServer>,
+    mut toasts: ResMut<Toasts>,
+) {
+    for event in asset_events.read() {
+        let AssetEvent::Modified { id } = event else {
+            continue;
+        };
+        if let Some(path) = asset_server.get_path(*id) {
+            toasts.info(format!("Reloaded {path}"));
+        }
+    }
+    // A broken definition keeps its previous values, so the game can go on while the file is fixed.
+    for AssetLoadFailedEvent { path, error, .. } in failed_events.read() {
+        toasts.error(format!("Could not reload {path}: {error}"));
+    }
+}
@@ -0,0 +1,28 @@
+use bevy::{
+    app::{Plugin, Update},
+    prelude::{in_state, IntoSystemConfigs},
+};
+
+use crate::gameplay_plugin::GameplayStates;
+
+use self::systems::{build_improvements, continue_construction, damage_improvements, produce};
+
+mod systems;
+
+/// Builds, completes and destroys improvements on tiles and tile connections, paid with the production factions gain from their tiles.
+pub(super) struct ImprovementPlugin;
+
+impl Plugin for ImprovementPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(
+            Update,
+            (
+                produce,
+                build_improvements,
+                continue_construction,
+                damage_improvements,
+            )
+                .run_if(in_state(GameplayStates::InGame)),
+        );
+    }
+}
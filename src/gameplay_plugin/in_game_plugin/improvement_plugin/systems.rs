@@ -0,0 +1,164 @@
+use bevy::{
+    asset::Assets,
+    core::Name,
+    hierarchy::{BuildChildren, Children, DespawnRecursiveExt},
+    log::warn,
+    prelude::{Commands, Entity, EventReader, Query, Res, ResMut, With, Without},
+    sprite::{Sprite, SpriteBundle},
+    transform::components::Transform,
+};
+
+use crate::gameplay_plugin::{
+    assets::{self, Placement},
+    components::{ConnectedTiles, Improvement, TileType},
+    events::{BuildImprovement, TileFoughtOver, TurnEnded},
+    improvements,
+    resources::{HexagonalMap, ImprovementTypes, Production, Tile, TileControl},
+};
+
+/// The production a faction gains each turn for every tile it controls.
+const PRODUCTION_PER_TILE: u32 = 2;
+
+/// Let every faction gain production for the tiles it controls at the end of a turn.
+pub(super) fn produce(
+    mut turn_ended_events: EventReader<TurnEnded>,
+    tile_control: Res<TileControl>,
+    mut production: ResMut<Production>,
+) {
+    for _ in turn_ended_events.read() {
+        for controller in tile_control
+            .0
+            .iter()
+            .filter_map(|(controller, _)| controller.as_ref())
+        {
+            production.gain(controller, PRODUCTION_PER_TILE);
+        }
+    }
+}
+
+/// Start building the ordered improvements, paid with the production of the ordering faction.
+pub(super) fn build_improvements(
+    mut commands: Commands,
+    mut build_improvement_events: EventReader<BuildImprovement>,
+    improvement_types: Res<ImprovementTypes>,
+    improvement_types_data: Res<Assets<assets::ImprovementType>>,
+    tiles: Query<(), With<TileType>>,
+    tile_connections: Query<(), (With<ConnectedTiles>, Without<TileType>)>,
+    children: Query<&Children>,
+    existing_improvements: Query<&Improvement>,
+    mut production: ResMut<Production>,
+) {
+    for BuildImprovement {
+        target,
+        improvement_type,
+        faction,
+    } in build_improvement_events.read()
+    {
+        let Some(improvement_type_data) =
+            improvement_types.get(improvement_type).and_then(|handle| {
+                improvement_types_data
+                    .get(handle)
+                    .map(|data| (handle, data))
+            })
+        else {
+            warn!("Can not build the unknown improvement type `{improvement_type}`");
+            continue;
+        };
+        let (improvement_type_asset, improvement_type_data) = improvement_type_data;
+        let valid_target = match improvement_type_data.placement() {
+            Placement::Tile => tiles.contains(*target),
+            // Connections at the edge of the map don't lead anywhere.
+            Placement::Connection => tile_connections.contains(*target),
+        };
+        if !valid_target {
+            warn!("Can not build `{improvement_type}` on {target}");
+            continue;
+        }
+        let already_built = children
+            .get(*target)
+            .into_iter()
+            .flatten()
+            .filter_map(|child| existing_improvements.get(*child).ok())
+            .any(|improvement| improvement.improvement_type() == improvement_type);
+        if already_built {
+            warn!("There already is a `{improvement_type}` on {target}");
+            continue;
+        }
+        if !production.spend(faction, u32::from(improvement_type_data.cost())) {
+            warn!("`{faction}` does not have the production to build `{improvement_type}`");
+            continue;
+        }
+
+        let improvement = Improvement::new(
+            improvement_type.clone(),
+            improvement_type_asset.clone(),
+            improvement_type_data.build_turns(),
+            0,
+        );
+        commands
+            .spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: improvements::tint(&improvement),
+                        ..Default::default()
+                    },
+                    texture: improvement_type_data.texture().clone(),
+                    transform: Transform::from_translation(improvements::OFFSET),
+                    ..Default::default()
+                },
+                improvement,
+                Name::new(improvement_type.clone()),
+            ))
+            .set_parent(*target);
+    }
+}
+
+pub(super) fn continue_construction(
+    mut turn_ended_events: EventReader<TurnEnded>,
+    mut improvements: Query<(&mut Improvement, &mut Sprite)>,
+) {
+    for _ in turn_ended_events.read() {
+        for (mut improvement, mut sprite) in &mut improvements {
+            if improvement.build() {
+                sprite.color = improvements::tint(&improvement);
+            }
+        }
+    }
+}
+
+/// Damage the improvements on a tile fought over and on the connection it was attacked across.
+pub(super) fn damage_improvements(
+    mut commands: Commands,
+    mut tile_fought_over_events: EventReader<TileFoughtOver>,
+    hexagonal_map: Res<HexagonalMap<Tile>>,
+    children: Query<&Children>,
+    mut improvements: Query<(Entity, &mut Improvement)>,
+    improvement_types: Res<Assets<assets::ImprovementType>>,
+) {
+    for TileFoughtOver {
+        coordinates,
+        attacker_coordinates,
+        damage,
+    } in tile_fought_over_events.read()
+    {
+        let targets = [
+            hexagonal_map.get(*coordinates).map(Tile::tile_entity),
+            hexagonal_map.connection_between(*coordinates, *attacker_coordinates),
+        ];
+        for target in targets.into_iter().flatten() {
+            for child in children.get(target).into_iter().flatten() {
+                let Ok((improvement_entity, mut improvement)) = improvements.get_mut(*child) else {
+                    continue;
+                };
+                let Some(improvement_type) =
+                    improvement_types.get(improvement.improvement_type_data())
+                else {
+                    continue;
+                };
+                if improvement.damage(*damage, improvement_type.durability()) {
+                    commands.entity(improvement_entity).despawn_recursive();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use bevy::{
+    app::{Plugin, Update},
+    input::{common_conditions::input_just_pressed, keyboard::KeyCode},
+    prelude::{in_state, IntoSystemConfigs, OnEnter, ReflectResource},
+};
+
+use crate::{
+    cleanup::Cleanup,
+    gameplay_plugin::{influence_map::InfluenceDecay, GameplayStates},
+};
+
+use self::{
+    resources::{FrontLineOverlay, FrontLines},
+    systems::{
+        draw_front_lines, map_front_lines, map_initial_front_lines, toggle_front_line_overlay,
+    },
+};
+
+mod resources;
+mod systems;
+
+/// Maps the influence of every faction each turn and draws the front lines between them.
+pub(super) struct InfluencePlugin;
+
+impl Plugin for InfluencePlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(OnEnter(GameplayStates::InGame), map_initial_front_lines)
+            .add_systems(
+                Update,
+                (
+                    map_front_lines,
+                    toggle_front_line_overlay.run_if(input_just_pressed(KeyCode::F3)),
+                    draw_front_lines,
+                )
+                    .chain()
+                    .run_if(in_state(GameplayStates::InGame)),
+            );
+        app.register_type::<InfluenceDecay>()
+            .register_type_data::<InfluenceDecay, ReflectResource>()
+            .init_resource::<InfluenceDecay>();
+        app.init_resource::<FrontLines>()
+            .reset_resource::<FrontLines>(GameplayStates::InGame);
+        app.init_resource::<FrontLineOverlay>()
+            .reset_resource::<FrontLineOverlay>(GameplayStates::InGame);
+    }
+}
@@ -0,0 +1,11 @@
+use bevy::prelude::Resource;
+
+use crate::gameplay_plugin::{influence_map::InfluenceLayers, save_file::FactionId};
+
+/// The influence layers from the perspective of every faction, mapped at the start of each turn.
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct FrontLines(pub Vec<(FactionId, InfluenceLayers)>);
+
+/// Whether the front lines between the factions are drawn onto the map.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrontLineOverlay(pub bool);
@@ -0,0 +1,179 @@
+use bevy::{
+    asset::Assets,
+    color::Color,
+    math::Vec2,
+    prelude::{EventReader, Gizmos, Query, Res, ResMut},
+};
+
+use crate::gameplay_plugin::{
+    assets::{self, MovementClass},
+    components::{AxialCoordinates, Owner, Unit, TILE_CORNERS},
+    events::TurnEnded,
+    in_game_plugin::movement_plugin::MovementRules,
+    influence_map::{InfluenceDecay, InfluenceMap, InfluenceSource},
+    resources::{Factions, TileControl},
+};
+
+use super::resources::{FrontLineOverlay, FrontLines};
+
+/// The strength every controlled tile projects for its controller, which is small compared to units.
+const CONTROLLED_TILE_STRENGTH: f32 = 0.5;
+/// How far the overlay is drawn inside of a front-line tile with the least and the most tension.
+const OVERLAY_INSET: (f32, f32) = (0.3, 0.75);
+/// Marks front-line tiles where the weaker side has more than a third of the strength, so they are contested.
+const CONTESTED_COLOR: Color = Color::WHITE;
+const CONTESTED_RADIUS: f32 = 3.0;
+
+/// Spread the strength of all units and controlled tiles over the map and derive the layers of every faction from it.
+/// Units project their damage, reduced by the health they lost.
+fn map_influence(
+    factions: &Factions,
+    units: &Query<(&Unit, &AxialCoordinates, &Owner)>,
+    unit_types: &Assets<assets::UnitType>,
+    tile_control: &TileControl,
+    movement_rules: &MovementRules,
+    influence_decay: InfluenceDecay,
+) -> FrontLines {
+    let faction_index = |faction: &str| factions.0.iter().position(|other| other.id() == faction);
+    let unit_sources = units
+        .iter()
+        .filter_map(|(unit, coordinates, Owner(owner))| {
+            let unit_type = unit_types.get(unit.unit_type_data())?;
+            let health = if unit_type.max_health() == 0 {
+                0.0
+            } else {
+                f32::from(unit.health(unit_type)) / f32::from(unit_type.max_health())
+            };
+            Some(InfluenceSource {
+                faction: faction_index(owner)?,
+                coordinates: *coordinates,
+                strength: (unit.attack_damage(unit_type) + unit.defense_damage(unit_type)) / 2.0
+                    * health,
+            })
+        });
+    let tile_sources = movement_rules
+        .hexagonal_map()
+        .iter()
+        .filter_map(|(_, coordinates)| {
+            Some(InfluenceSource {
+                faction: faction_index(tile_control.controller(coordinates)?)?,
+                coordinates,
+                strength: CONTROLLED_TILE_STRENGTH,
+            })
+        });
+    let influence_map = InfluenceMap::new(
+        movement_rules.hexagonal_map().radius(),
+        unit_sources.chain(tile_sources),
+        influence_decay,
+        |from, to| movement_rules.step_cost(MovementClass::Foot, from, to),
+    );
+    FrontLines(
+        factions
+            .0
+            .iter()
+            .enumerate()
+            .map(|(index, faction)| (faction.id().clone(), influence_map.layers(index)))
+            .collect(),
+    )
+}
+
+/// Map the front lines when a game starts.
+pub(super) fn map_initial_front_lines(
+    mut front_lines: ResMut<FrontLines>,
+    factions: Res<Factions>,
+    units: Query<(&Unit, &AxialCoordinates, &Owner)>,
+    unit_types: Res<Assets<assets::UnitType>>,
+    tile_control: Res<TileControl>,
+    movement_rules: MovementRules,
+    influence_decay: Res<InfluenceDecay>,
+) {
+    *front_lines = map_influence(
+        &factions,
+        &units,
+        &unit_types,
+        &tile_control,
+        &movement_rules,
+        *influence_decay,
+    );
+}
+
+/// Map the front lines again at the start of every turn, after units moved and tiles were captured.
+pub(super) fn map_front_lines(
+    mut turn_ended_events: EventReader<TurnEnded>,
+    mut front_lines: ResMut<FrontLines>,
+    factions: Res<Factions>,
+    units: Query<(&Unit, &AxialCoordinates, &Owner)>,
+    unit_types: Res<Assets<assets::UnitType>>,
+    tile_control: Res<TileControl>,
+    movement_rules: MovementRules,
+    influence_decay: Res<InfluenceDecay>,
+) {
+    for _ in turn_ended_events.read() {
+        *front_lines = map_influence(
+            &factions,
+            &units,
+            &unit_types,
+            &tile_control,
+            &movement_rules,
+            *influence_decay,
+        );
+    }
+}
+
+/// Toggle the front-line overlay.
+pub(super) fn toggle_front_line_overlay(mut front_line_overlay: ResMut<FrontLineOverlay>) {
+    front_line_overlay.0 = !front_line_overlay.0;
+}
+
+/// Draw a hexagon in the color of each faction on the front-line tiles it dominates, which is larger the more tension lies on the tile.
+/// Contested tiles are marked.
+pub(super) fn draw_front_lines(
+    mut gizmos: Gizmos,
+    front_line_overlay: Res<FrontLineOverlay>,
+    front_lines: Res<FrontLines>,
+    factions: Res<Factions>,
+) {
+    if !front_line_overlay.0 {
+        return;
+    }
+    let most_tension = front_lines
+        .0
+        .iter()
+        .flat_map(|(_, layers)| layers.tension.iter().map(|(tension, _)| *tension))
+        .fold(0.0, f32::max);
+    for (faction, layers) in &front_lines.0 {
+        let Some(faction) = factions.get(faction) else {
+            continue;
+        };
+        for (on_front_line, coordinates) in layers.frontline.iter() {
+            let balance = layers.balance.get(coordinates).copied().unwrap_or_default();
+            if !on_front_line || balance <= 0.0 {
+                continue;
+            }
+            let tension = layers.tension.get(coordinates).copied().unwrap_or_default();
+            let share = if most_tension > 0.0 {
+                tension / most_tension
+            } else {
+                0.0
+            };
+            let inset = OVERLAY_INSET.0 + (OVERLAY_INSET.1 - OVERLAY_INSET.0) * share;
+            let center = Vec2::from(coordinates);
+            for (index, corner) in TILE_CORNERS.iter().enumerate() {
+                let next_corner = TILE_CORNERS[(index + 1) % TILE_CORNERS.len()];
+                gizmos.line_2d(
+                    center + *corner * inset,
+                    center + next_corner * inset,
+                    faction.color(),
+                );
+            }
+            let vulnerability = layers
+                .vulnerability
+                .get(coordinates)
+                .copied()
+                .unwrap_or_default();
+            if vulnerability > balance.abs() {
+                gizmos.circle_2d(center, CONTESTED_RADIUS, CONTESTED_COLOR);
+            }
+        }
+    }
+}
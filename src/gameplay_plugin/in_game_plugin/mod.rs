@@ -4,33 +4,72 @@ use bevy::{
     prelude::{in_state, IntoSystemConfigs, KeyCode, NextState, OnEnter, Res, ResMut},
 };
 
-use systems::setup;
+use systems::{cache_tile_movement_costs, setup};
 
 use crate::GameStates;
 
-use self::camera_plugin::CameraPlugin;
+use self::{
+    camera_plugin::CameraPlugin, combat_plugin::CombatPlugin, control_plugin::ControlPlugin,
+    entrenchment_plugin::EntrenchmentPlugin, hot_reload_plugin::HotReloadPlugin,
+    improvement_plugin::ImprovementPlugin, influence_plugin::InfluencePlugin,
+    movement_plugin::MovementPlugin, orders_plugin::OrdersPlugin, save_game_plugin::SaveGamePlugin,
+    supply_plugin::SupplyPlugin, terrain_plugin::TerrainPlugin,
+    tile_sprite_plugin::TileSpritePlugin, turn_plugin::TurnPlugin, weather_plugin::WeatherPlugin,
+};
 
 use super::GameplayStates;
 
 mod camera_plugin;
+mod combat_plugin;
+mod control_plugin;
+mod entrenchment_plugin;
+mod hot_reload_plugin;
+mod improvement_plugin;
+mod influence_plugin;
+mod movement_plugin;
+mod orders_plugin;
+mod save_game_plugin;
+mod supply_plugin;
 mod systems;
+mod terrain_plugin;
+mod tile_sprite_plugin;
+mod turn_plugin;
+mod weather_plugin;
 
 pub(super) struct InGamePlugin;
 
 impl Plugin for InGamePlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.add_plugins(CameraPlugin);
-
-        app.add_systems(OnEnter(GameplayStates::InGame), setup)
-            .add_systems(
-                Update,
-                (|keys: Res<ButtonInput<KeyCode>>,
-                  mut game_states: ResMut<NextState<GameStates>>| {
-                    if keys.as_ref().pressed(KeyCode::Escape) {
-                        game_states.as_mut().set(GameStates::MainMenu)
-                    }
-                })
-                .run_if(in_state(GameplayStates::InGame)),
-            );
+        app.add_plugins((
+            CameraPlugin,
+            CombatPlugin,
+            ControlPlugin,
+            EntrenchmentPlugin,
+            HotReloadPlugin,
+            InfluencePlugin,
+            ImprovementPlugin,
+            MovementPlugin,
+            OrdersPlugin,
+            SaveGamePlugin,
+            SupplyPlugin,
+            TerrainPlugin,
+            TileSpritePlugin,
+            TurnPlugin,
+            WeatherPlugin,
+        ));
+
+        app.add_systems(
+            OnEnter(GameplayStates::InGame),
+            (setup, cache_tile_movement_costs),
+        )
+        .add_systems(
+            Update,
+            (|keys: Res<ButtonInput<KeyCode>>, mut game_states: ResMut<NextState<GameStates>>| {
+                if keys.as_ref().pressed(KeyCode::Escape) {
+                    game_states.as_mut().set(GameStates::MainMenu)
+                }
+            })
+            .run_if(in_state(GameplayStates::InGame)),
+        );
     }
 }
@@ -0,0 +1,36 @@
+use std::time::Duration;
+
+use bevy::{
+    app::{Plugin, Update},
+    prelude::{in_state, IntoSystemConfigs},
+    time::common_conditions::on_timer,
+};
+
+use crate::gameplay_plugin::GameplayStates;
+
+use self::systems::{follow_move_orders, plan_moves};
+
+mod systems;
+
+pub(super) use self::systems::{refresh_movement_points, MovementRules};
+
+/// How long a unit takes to move to the next tile of its path.
+const STEP_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Moves units tile by tile along the paths of their move orders, spending their movement points.
+pub(super) struct MovementPlugin;
+
+impl Plugin for MovementPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(
+            Update,
+            (
+                refresh_movement_points,
+                plan_moves,
+                follow_move_orders.run_if(on_timer(STEP_INTERVAL)),
+            )
+                .chain()
+                .run_if(in_state(GameplayStates::InGame)),
+        );
+    }
+}
@@ -0,0 +1,293 @@
+use bevy::{
+    asset::Assets,
+    ecs::system::SystemParam,
+    hierarchy::Children,
+    log::warn,
+    prelude::{Commands, Entity, EventReader, EventWriter, Query, Res},
+    transform::components::Transform,
+};
+
+use crate::gameplay_plugin::{
+    assets::{self, MovementClass},
+    components::{
+        AxialCoordinates, ConnectionType, Elevation, Improvement, MoveOrder, Owner, Unit,
+    },
+    events::{MoveUnit, TileEntered, TurnEnded},
+    movement,
+    pathfinding::{movement_costs_from, path_to, MovementCost},
+    resources::{HexagonalMap, Tile, TileMovementCosts},
+    save_file::FactionId,
+    units,
+};
+
+/// Looks up the cost of moving between adjacent tiles from the tiles, their connections and the improvements on them.
+#[derive(SystemParam)]
+pub(in crate::gameplay_plugin::in_game_plugin) struct MovementRules<'w, 's> {
+    hexagonal_map: Res<'w, HexagonalMap<Tile>>,
+    tile_movement_costs: Res<'w, TileMovementCosts>,
+    elevations: Query<'w, 's, &'static Elevation>,
+    tile_connections: Query<'w, 's, &'static ConnectionType>,
+    children: Query<'w, 's, &'static Children>,
+    improvements: Query<'w, 's, &'static Improvement>,
+    connection_types: Res<'w, Assets<assets::ConnectionType>>,
+    improvement_types: Res<'w, Assets<assets::ImprovementType>>,
+}
+
+impl MovementRules<'_, '_> {
+    pub fn hexagonal_map(&self) -> &HexagonalMap<Tile> {
+        &self.hexagonal_map
+    }
+
+    /// The children of `entity`, e.g. the improvements on a tile or tile connection.
+    pub fn children(&self, entity: Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.children.get(entity).into_iter().flatten().copied()
+    }
+
+    pub fn elevation(&self, coordinates: AxialCoordinates) -> Option<Elevation> {
+        let tile = self.hexagonal_map.get(coordinates)?;
+        self.elevations.get(tile.tile_entity()).ok().copied()
+    }
+
+    /// The type of the connection between two adjacent tiles.
+    /// `None` for plain borders and connections negated by a built improvement like a bridge.
+    pub fn connection_type_between(
+        &self,
+        from: AxialCoordinates,
+        to: AxialCoordinates,
+    ) -> Option<&assets::ConnectionType> {
+        let tile_connection = self.hexagonal_map.connection_between(from, to)?;
+        let negated = self
+            .children
+            .get(tile_connection)
+            .into_iter()
+            .flatten()
+            .filter_map(|child| self.improvements.get(*child).ok())
+            .filter(|improvement| improvement.is_built())
+            .filter_map(|improvement| {
+                self.improvement_types
+                    .get(improvement.improvement_type_data())
+            })
+            .any(assets::ImprovementType::negates_connection);
+        if negated {
+            return None;
+        }
+        let connection_type = self.tile_connections.get(tile_connection).ok()?;
+        self.connection_types
+            .get(connection_type.connection_type_data())
+    }
+
+    /// The movement cost for a unit of `movement_class` to step from `from` onto the adjacent tile `to`, see [movement::step_cost()].
+    pub fn step_cost(
+        &self,
+        movement_class: MovementClass,
+        from: AxialCoordinates,
+        to: AxialCoordinates,
+    ) -> Option<MovementCost> {
+        movement::step_cost(
+            movement_class,
+            self.tile_movement_costs.0.get(to)?,
+            self.connection_type_between(from, to),
+            self.elevation(from)?,
+            self.elevation(to)?,
+        )
+    }
+
+    /// Whether the tile at `coordinates` lies in the zone of control of one of the `units` not belonging to `faction`, see [movement::in_enemy_zone_of_control()].
+    pub fn in_enemy_zone_of_control<'a>(
+        &self,
+        coordinates: AxialCoordinates,
+        faction: &FactionId,
+        units: impl IntoIterator<Item = (AxialCoordinates, &'a FactionId)>,
+    ) -> bool {
+        movement::in_enemy_zone_of_control(coordinates, faction, units, |from, to| {
+            self.connection_type_between(from, to)
+                .is_some_and(assets::ConnectionType::negates_zone_of_control)
+        })
+    }
+
+    /// [Self::step_cost()] plus the cost of leaving an enemy zone of control, if `from` lies in one.
+    pub fn step_cost_under_zone_of_control<'a>(
+        &self,
+        movement_class: MovementClass,
+        from: AxialCoordinates,
+        to: AxialCoordinates,
+        faction: &FactionId,
+        units: impl IntoIterator<Item = (AxialCoordinates, &'a FactionId)>,
+    ) -> Option<MovementCost> {
+        let step_cost = self.step_cost(movement_class, from, to)?;
+        if self.in_enemy_zone_of_control(from, faction, units) {
+            Some(step_cost + movement::ZONE_OF_CONTROL_EXIT_COST)
+        } else {
+            Some(step_cost)
+        }
+    }
+}
+
+pub(in crate::gameplay_plugin::in_game_plugin) fn refresh_movement_points(
+    mut turn_ended_events: EventReader<TurnEnded>,
+    mut units: Query<&mut Unit>,
+) {
+    for _ in turn_ended_events.read() {
+        for mut unit in &mut units {
+            unit.refresh_movement_points();
+        }
+    }
+}
+
+/// Find the cheapest path for every ordered move.
+/// Paths never lead through tiles occupied by enemies or stacks without room for the unit, and leaving enemy zones of control costs extra.
+pub(super) fn plan_moves(
+    mut commands: Commands,
+    mut move_unit_events: EventReader<MoveUnit>,
+    units: Query<(&Unit, &AxialCoordinates, &Owner)>,
+    unit_types: Res<Assets<assets::UnitType>>,
+    movement_rules: MovementRules,
+) {
+    for MoveUnit { unit, destination } in move_unit_events.read() {
+        let Ok((unit_data, origin, Owner(faction))) = units.get(*unit) else {
+            warn!("Can not move {unit}, which is not a unit");
+            continue;
+        };
+        let Some(unit_type) = unit_types.get(unit_data.unit_type_data()) else {
+            continue;
+        };
+        let positions: Vec<_> = units
+            .iter()
+            .map(|(_, coordinates, Owner(owner))| (*coordinates, owner))
+            .collect();
+        let cost = |from: AxialCoordinates, to: AxialCoordinates| {
+            let occupied_by_enemy = positions
+                .iter()
+                .any(|(coordinates, owner)| *coordinates == to && *owner != faction);
+            if occupied_by_enemy
+                || movement::room_on_tile(to, faction, positions.iter().copied()) == 0
+            {
+                return None;
+            }
+            movement_rules.step_cost_under_zone_of_control(
+                unit_type.movement_class(),
+                from,
+                to,
+                faction,
+                positions.iter().copied(),
+            )
+        };
+        let costs = movement_costs_from(
+            movement_rules.hexagonal_map().radius(),
+            [*origin],
+            MovementCost::MAX,
+            &cost,
+        );
+        match path_to(&costs, *destination, &cost) {
+            Some(path) if !path.is_empty() => {
+                commands.entity(*unit).insert(MoveOrder(path.into()));
+            }
+            Some(_) => {
+                commands.entity(*unit).remove::<MoveOrder>();
+            }
+            None => warn!("{unit} can not reach {destination:?}"),
+        }
+    }
+}
+
+/// Move every unit with a move order one tile further, if it has the movement points left.
+/// A unit that has not moved this turn can always take one step, so it is never stuck in terrain costing more than its movement points.
+/// Entering an enemy zone of control ends the movement of a unit for the turn.
+/// The order ends at its destination or when the path is blocked by an enemy. Units wait in front of stacks without room for them.
+pub(super) fn follow_move_orders(
+    mut commands: Commands,
+    mut units: Query<(
+        Entity,
+        &mut Unit,
+        &mut AxialCoordinates,
+        &mut Transform,
+        &Owner,
+        Option<&mut MoveOrder>,
+    )>,
+    unit_types: Res<Assets<assets::UnitType>>,
+    movement_rules: MovementRules,
+    mut tile_entered_events: EventWriter<TileEntered>,
+) {
+    let mut positions: Vec<(Entity, AxialCoordinates, FactionId)> = units
+        .iter()
+        .map(|(unit, _, coordinates, _, Owner(owner), _)| (unit, *coordinates, owner.clone()))
+        .collect();
+    for (unit, mut unit_data, mut coordinates, mut transform, Owner(faction), move_order) in
+        &mut units
+    {
+        let Some(mut move_order) = move_order else {
+            continue;
+        };
+        let Some(unit_type) = unit_types.get(unit_data.unit_type_data()) else {
+            continue;
+        };
+        let Some(next) = move_order.0.front().copied() else {
+            commands.entity(unit).remove::<MoveOrder>();
+            continue;
+        };
+        let occupied_by_enemy = positions
+            .iter()
+            .any(|(_, position, owner)| *position == next && owner != faction);
+        let room = movement::room_on_tile(
+            next,
+            faction,
+            positions
+                .iter()
+                .map(|(_, position, owner)| (*position, owner)),
+        );
+        if !occupied_by_enemy && room == 0 {
+            // Wait until units of the stack move on.
+            continue;
+        }
+        let step_cost = (!occupied_by_enemy)
+            .then(|| {
+                movement_rules.step_cost_under_zone_of_control(
+                    unit_type.movement_class(),
+                    *coordinates,
+                    next,
+                    faction,
+                    positions
+                        .iter()
+                        .map(|(_, position, owner)| (*position, owner)),
+                )
+            })
+            .flatten();
+        let Some(step_cost) = step_cost else {
+            warn!("The path of {unit} to {next:?} is blocked");
+            commands.entity(unit).remove::<MoveOrder>();
+            continue;
+        };
+        let step_cost = u8::try_from(step_cost).unwrap_or(u8::MAX);
+        if unit_data.remaining_movement_points(unit_type) < step_cost
+            && unit_data.spent_movement_points() > 0
+        {
+            // Continue next turn.
+            continue;
+        }
+
+        unit_data.spend_movement_points(step_cost);
+        unit_data.leave_position();
+        *coordinates = next;
+        *transform = units::transform(next);
+        move_order.0.pop_front();
+        if let Some((_, position, _)) = positions.iter_mut().find(|(other, ..)| *other == unit) {
+            *position = next;
+        }
+        tile_entered_events.send(TileEntered {
+            coordinates: next,
+            faction: faction.clone(),
+        });
+        if move_order.0.is_empty() {
+            commands.entity(unit).remove::<MoveOrder>();
+        } else if movement_rules.in_enemy_zone_of_control(
+            next,
+            faction,
+            positions
+                .iter()
+                .map(|(_, position, owner)| (*position, owner)),
+        ) {
+            // Continue next turn.
+            unit_data.spend_movement_points(u8::MAX);
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use bevy::{
+    app::{Plugin, Update},
+    prelude::{in_state, IntoSystemConfigs},
+};
+
+use crate::{cleanup::Cleanup, gameplay_plugin::GameplayStates};
+
+use self::{
+    resources::{HoveredTile, Selection},
+    systems::{
+        attack_preview, draw_selection, give_orders, hover_tile, order_of_battle, selection_info,
+        tile_orders,
+    },
+};
+
+mod resources;
+mod systems;
+
+/// Lets the player select units or whole formations of the faction whose turn it is, inspect them, order them to move or attack, preview the outcome of attacks and order improvements and terrain changes on the tiles they control.
+pub(super) struct OrdersPlugin;
+
+impl Plugin for OrdersPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(
+            Update,
+            (
+                hover_tile,
+                order_of_battle,
+                give_orders,
+                draw_selection,
+                selection_info,
+                attack_preview,
+                tile_orders,
+            )
+                .chain()
+                .run_if(in_state(GameplayStates::InGame)),
+        );
+        app.init_resource::<HoveredTile>()
+            .reset_resource::<HoveredTile>(GameplayStates::InGame);
+        app.init_resource::<Selection>()
+            .reset_resource::<Selection>(GameplayStates::InGame);
+    }
+}
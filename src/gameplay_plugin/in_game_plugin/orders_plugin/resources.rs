@@ -0,0 +1,11 @@
+use bevy::prelude::{Entity, Resource};
+
+use crate::gameplay_plugin::components::AxialCoordinates;
+
+/// The tile under the cursor. `None` if the cursor is outside of the map or over the UI.
+#[derive(Resource, Default, Debug, Clone, PartialEq, Eq)]
+pub struct HoveredTile(pub Option<AxialCoordinates>);
+
+/// The units the player gives orders to as one, which may stand on several tiles when a formation is selected.
+#[derive(Resource, Default, Debug, Clone, PartialEq, Eq)]
+pub struct Selection(pub Vec<Entity>);
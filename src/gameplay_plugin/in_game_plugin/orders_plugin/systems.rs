@@ -0,0 +1,625 @@
+use std::ops::RangeInclusive;
+
+use bevy::{
+    asset::Assets,
+    color::Color,
+    ecs::system::SystemParam,
+    hierarchy::{Children, HierarchyQueryExt, Parent},
+    input::{mouse::MouseButton, ButtonInput},
+    math::Vec2,
+    prelude::{Entity, EventWriter, Gizmos, Query, Res, ResMut, With, Without},
+    render::camera::Camera,
+    transform::components::GlobalTransform,
+    window::Window,
+};
+use bevy_egui::{
+    egui::{Align2, Button, CollapsingHeader, Ui, Window as EguiWindow},
+    EguiContext,
+};
+use bevy_window::PrimaryWindow;
+
+use crate::gameplay_plugin::{
+    assets,
+    assets::{Placement, TransitionTrigger},
+    components::{
+        AxialCoordinates, ConnectionType, Formation, Improvement, Owner, TerrainWork, TileType,
+        Unit, TILE_CORNERS,
+    },
+    events::{Attack, BuildImprovement, MoveUnit, StartTerrainWork},
+    in_game_plugin::{camera_plugin::MainCamera, combat_plugin::CombatRules},
+    movement,
+    resources::{
+        ActiveFaction, Factions, HexagonalMap, ImprovementTypes, Production, Tile, TileControl,
+        TileTypes,
+    },
+    save_file::ImprovementTypePath,
+};
+
+use super::resources::{HoveredTile, Selection};
+
+/// How far the outline of selected tiles is drawn inside of the tiles, so it doesn't hide the borders of factions.
+const SELECTION_INSET: f32 = 0.8;
+const SELECTION_COLOR: Color = Color::WHITE;
+/// The directions of the neighbours of a tile, in the order of [AxialCoordinates::neighbours].
+const DIRECTIONS: [&str; 6] = [
+    "east",
+    "south-east",
+    "south-west",
+    "west",
+    "north-west",
+    "north-east",
+];
+
+/// Find the tile under the cursor.
+pub(super) fn hover_tile(
+    mut windows: Query<(&Window, &mut EguiContext), With<PrimaryWindow>>,
+    camera: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    hexagonal_map: Res<HexagonalMap<Tile>>,
+    mut hovered_tile: ResMut<HoveredTile>,
+) {
+    let Ok((window, mut egui_context)) = windows.get_single_mut() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera.get_single() else {
+        return;
+    };
+    hovered_tile.0 = window
+        .cursor_position()
+        .filter(|_| !egui_context.get_mut().is_pointer_over_area())
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor))
+        .map(AxialCoordinates::from)
+        .filter(|coordinates| hexagonal_map.contains(*coordinates));
+}
+
+/// Select the units of the faction whose turn it is on a tile with a left click.
+/// A right click orders the selected units to attack the clicked tile if enemies are on it, or to move there otherwise.
+/// Moving units which don't fit onto the stack of the clicked tile are spread over its neighbours.
+pub(super) fn give_orders(
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    hovered_tile: Res<HoveredTile>,
+    hexagonal_map: Res<HexagonalMap<Tile>>,
+    mut selection: ResMut<Selection>,
+    active_faction: Res<ActiveFaction>,
+    units: Query<(Entity, &AxialCoordinates, &Owner), With<Unit>>,
+    mut move_unit_events: EventWriter<MoveUnit>,
+    mut attack_events: EventWriter<Attack>,
+) {
+    // Selected units may have been destroyed, or the turn passed on to another faction.
+    selection.0.retain(|unit| {
+        units
+            .get(*unit)
+            .is_ok_and(|(_, _, Owner(owner))| active_faction.is(owner))
+    });
+    let Some(hovered_tile) = hovered_tile.0 else {
+        return;
+    };
+    if mouse_buttons.just_pressed(MouseButton::Left) {
+        selection.0 = units
+            .iter()
+            .filter(|(_, coordinates, Owner(owner))| {
+                **coordinates == hovered_tile && active_faction.is(owner)
+            })
+            .map(|(unit, ..)| unit)
+            .collect();
+    }
+    if !mouse_buttons.just_pressed(MouseButton::Right) {
+        return;
+    }
+    let Some((_, _, Owner(faction))) = selection.0.first().and_then(|unit| units.get(*unit).ok())
+    else {
+        return;
+    };
+    let enemies_on_tile = units
+        .iter()
+        .any(|(_, coordinates, Owner(owner))| *coordinates == hovered_tile && owner != faction);
+    if enemies_on_tile {
+        attack_events.send(Attack {
+            attackers: selection.0.clone(),
+            target: hovered_tile,
+        });
+    } else {
+        let others: Vec<_> = units
+            .iter()
+            .filter(|(unit, ..)| !selection.0.contains(unit))
+            .map(|(_, coordinates, Owner(owner))| (*coordinates, owner))
+            .collect();
+        let destinations =
+            movement::spread_over_stacks(hovered_tile, selection.0.len(), |coordinates| {
+                let occupied_by_enemy = others
+                    .iter()
+                    .any(|(position, owner)| *position == coordinates && *owner != faction);
+                if !hexagonal_map.contains(coordinates) || occupied_by_enemy {
+                    return 0;
+                }
+                movement::room_on_tile(coordinates, faction, others.iter().copied())
+            });
+        move_unit_events.send_batch(selection.0.iter().zip(destinations).map(
+            |(unit, destination)| MoveUnit {
+                unit: *unit,
+                destination,
+            },
+        ));
+    }
+}
+
+/// Outline the tiles of the selected units.
+pub(super) fn draw_selection(
+    mut gizmos: Gizmos,
+    selection: Res<Selection>,
+    units: Query<&AxialCoordinates, With<Unit>>,
+) {
+    let mut tiles: Vec<_> = units.iter_many(&selection.0).copied().collect();
+    tiles.sort_by_key(|coordinates| (coordinates.q(), coordinates.r()));
+    tiles.dedup();
+    for coordinates in tiles {
+        let center = Vec2::from(coordinates);
+        for (index, corner) in TILE_CORNERS.iter().enumerate() {
+            let next_corner = TILE_CORNERS[(index + 1) % TILE_CORNERS.len()];
+            gizmos.line_2d(
+                center + *corner * SELECTION_INSET,
+                center + next_corner * SELECTION_INSET,
+                SELECTION_COLOR,
+            );
+        }
+    }
+}
+
+/// Looks up the formations and the units they are made of.
+#[derive(SystemParam)]
+pub(super) struct OrderOfBattle<'w, 's> {
+    formations: Query<'w, 's, (&'static Formation, &'static Owner)>,
+    root_formations: Query<'w, 's, Entity, (With<Formation>, Without<Parent>)>,
+    children: Query<'w, 's, &'static Children>,
+    units: Query<'w, 's, &'static Unit>,
+    unit_types: Res<'w, Assets<assets::UnitType>>,
+    factions: Res<'w, Factions>,
+}
+
+impl OrderOfBattle<'_, '_> {
+    /// The units of `formation` and of all formations subordinate to it.
+    fn units_of(&self, formation: Entity) -> Vec<Entity> {
+        self.children
+            .iter_descendants(formation)
+            .filter(|descendant| self.units.contains(*descendant))
+            .collect()
+    }
+
+    /// Show `formation` with a button selecting all of its units, followed by its subordinate formations and units.
+    fn show(&self, ui: &mut Ui, formation: Entity, selection: &mut Selection) {
+        let Ok((formation_data, Owner(faction))) = self.formations.get(formation) else {
+            return;
+        };
+        let units = self.units_of(formation);
+        CollapsingHeader::new(format!(
+            "{} ({:?}, {})",
+            formation_data.display_name(),
+            formation_data.echelon(),
+            self.factions
+                .get(faction)
+                .map_or(faction.as_str(), |faction| faction.display_name())
+        ))
+        .id_source(formation)
+        .show(ui, |ui| {
+            let selected = !units.is_empty() && units.iter().all(|unit| selection.0.contains(unit));
+            if ui
+                .selectable_label(selected, format!("Select all {} units", units.len()))
+                .clicked()
+            {
+                selection.0 = units.clone();
+            }
+            for child in self.children.get(formation).into_iter().flatten() {
+                if self.formations.contains(*child) {
+                    self.show(ui, *child, selection);
+                } else if let Some(unit_type) = self
+                    .units
+                    .get(*child)
+                    .ok()
+                    .and_then(|unit| self.unit_types.get(unit.unit_type_data()))
+                {
+                    let selected = selection.0.contains(child);
+                    if ui
+                        .selectable_label(selected, unit_type.display_name())
+                        .clicked()
+                    {
+                        selection.0 = vec![*child];
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// The rank and experience of a unit of `unit_type`, with the experience it needs for its next rank and the abilities it unlocked.
+fn format_experience(unit: &Unit, unit_type: &assets::UnitType) -> String {
+    let rank = unit
+        .rank(unit_type)
+        .map_or("Recruit", assets::Rank::display_name);
+    let abilities: Vec<_> = unit_type
+        .ranks()
+        .iter()
+        .take_while(|rank| rank.experience() <= unit.experience())
+        .flat_map(|rank| rank.abilities())
+        .map(|ability| format!("{ability:?}"))
+        .collect();
+    let experience = match unit_type
+        .ranks()
+        .iter()
+        .find(|rank| rank.experience() > unit.experience())
+    {
+        Some(next_rank) => format!(
+            "{rank}, {}/{} experience",
+            unit.experience(),
+            next_rank.experience()
+        ),
+        None => format!("{rank}, {} experience", unit.experience()),
+    };
+    if abilities.is_empty() {
+        experience
+    } else {
+        format!("{experience} ({})", abilities.join(", "))
+    }
+}
+
+/// Show the health, organisation and experience of the selected units.
+pub(super) fn selection_info(
+    mut egui_context: Query<&mut EguiContext, With<PrimaryWindow>>,
+    selection: Res<Selection>,
+    units: Query<&Unit>,
+    unit_types: Res<Assets<assets::UnitType>>,
+) {
+    if selection.0.is_empty() {
+        return;
+    }
+    let Ok(mut egui_context) = egui_context.get_single_mut() else {
+        return;
+    };
+    EguiWindow::new("Selection")
+        .resizable(false)
+        .collapsible(false)
+        .anchor(Align2::CENTER_BOTTOM, [0.0, -8.0])
+        .show(egui_context.get_mut(), |ui| {
+            for unit in units.iter_many(&selection.0) {
+                let Some(unit_type) = unit_types.get(unit.unit_type_data()) else {
+                    continue;
+                };
+                ui.label(format!(
+                    "{}: {}/{} health, {}/{} organisation, {}",
+                    unit_type.display_name(),
+                    unit.health(unit_type),
+                    unit_type.max_health(),
+                    unit.organisation(unit_type),
+                    unit_type.max_organisation(),
+                    format_experience(unit, unit_type)
+                ));
+            }
+        });
+}
+
+/// Show the formations of the faction whose turn it is, which let the player select all units of a formation to order them as one.
+pub(super) fn order_of_battle(
+    mut egui_context: Query<&mut EguiContext, With<PrimaryWindow>>,
+    order_of_battle: OrderOfBattle,
+    active_faction: Res<ActiveFaction>,
+    mut selection: ResMut<Selection>,
+) {
+    // Subordinate formations and units belong to the faction of their root formation.
+    let mut roots: Vec<_> = order_of_battle
+        .root_formations
+        .iter()
+        .filter(|root| {
+            order_of_battle
+                .formations
+                .get(*root)
+                .is_ok_and(|(_, Owner(faction))| active_faction.is(faction))
+        })
+        .collect();
+    if roots.is_empty() {
+        return;
+    }
+    let Ok(mut egui_context) = egui_context.get_single_mut() else {
+        return;
+    };
+    roots.sort_by_key(|root| {
+        order_of_battle
+            .formations
+            .get(*root)
+            .map(|(formation, _)| formation.id().clone())
+            .ok()
+    });
+    EguiWindow::new("Order of battle")
+        .resizable(false)
+        .anchor(Align2::LEFT_TOP, [8.0, 8.0])
+        .show(egui_context.get_mut(), |ui| {
+            for root in roots {
+                order_of_battle.show(ui, root, &mut selection);
+            }
+        });
+}
+
+fn format_range(range: &RangeInclusive<u16>) -> String {
+    if range.start() == range.end() {
+        range.start().to_string()
+    } else {
+        format!("{}-{}", range.start(), range.end())
+    }
+}
+
+/// Show the expected outcome of an attack of the selected units onto the hovered tile.
+pub(super) fn attack_preview(
+    mut egui_context: Query<&mut EguiContext, With<PrimaryWindow>>,
+    hovered_tile: Res<HoveredTile>,
+    selection: Res<Selection>,
+    units: Query<(Entity, &Unit, &AxialCoordinates, &Owner)>,
+    combat_rules: CombatRules,
+) {
+    let Some(hovered_tile) = hovered_tile.0 else {
+        return;
+    };
+    if selection.0.is_empty() {
+        return;
+    }
+    let positions: Vec<_> = units
+        .iter()
+        .map(|(unit, unit_data, coordinates, Owner(owner))| (unit, unit_data, *coordinates, owner))
+        .collect();
+    let Some(engagement) = combat_rules.engagement(&selection.0, hovered_tile, &positions) else {
+        return;
+    };
+    let preview = engagement.preview();
+    let Ok(mut egui_context) = egui_context.get_single_mut() else {
+        return;
+    };
+    EguiWindow::new("Attack")
+        .resizable(false)
+        .collapsible(false)
+        .anchor(Align2::LEFT_BOTTOM, [8.0, -8.0])
+        .show(egui_context.get_mut(), |ui| {
+            ui.label(format!(
+                "{} against {} units, combat width {}",
+                engagement.attacking.len(),
+                engagement.defending.len(),
+                engagement.combat_width
+            ));
+            ui.label(format!(
+                "Enemy losses: {} health, {} organisation",
+                format_range(&preview.defender_losses.health),
+                format_range(&preview.defender_losses.organisation)
+            ));
+            ui.label(format!(
+                "Own losses: {} health, {} organisation",
+                format_range(&preview.attacker_losses.health),
+                format_range(&preview.attacker_losses.organisation)
+            ));
+            ui.label(format!(
+                "Chance the enemy retreats: {:.0}%",
+                preview.defender_retreat_chance * 100.0
+            ));
+        });
+}
+
+/// An improvement the player can order to be built.
+struct ConstructionSite {
+    target: Entity,
+    improvement_type: ImprovementTypePath,
+    label: String,
+    cost: u32,
+    already_built: bool,
+}
+
+/// Looks up the improvements which can be built on a tile and across its connections.
+#[derive(SystemParam)]
+pub(super) struct Construction<'w, 's> {
+    hexagonal_map: Res<'w, HexagonalMap<Tile>>,
+    improvement_types: Res<'w, ImprovementTypes>,
+    improvement_types_data: Res<'w, Assets<assets::ImprovementType>>,
+    connection_types: Query<'w, 's, &'static ConnectionType>,
+    connection_types_data: Res<'w, Assets<assets::ConnectionType>>,
+    children: Query<'w, 's, &'static Children>,
+    improvements: Query<'w, 's, &'static Improvement>,
+}
+
+impl Construction<'_, '_> {
+    fn already_built(&self, target: Entity, improvement_type: &str) -> bool {
+        self.children
+            .get(target)
+            .into_iter()
+            .flatten()
+            .filter_map(|child| self.improvements.get(*child).ok())
+            .any(|improvement| improvement.improvement_type() == improvement_type)
+    }
+
+    /// The improvements on the tile at `coordinates` and on the connections to its neighbours which have a connection type, like a river a bridge can be built across.
+    fn sites(&self, coordinates: AxialCoordinates) -> Vec<ConstructionSite> {
+        let Some(tile) = self.hexagonal_map.get(coordinates) else {
+            return Vec::new();
+        };
+        let mut improvement_types: Vec<_> = self.improvement_types.0.iter().collect();
+        improvement_types.sort_by_key(|(improvement_type, _)| *improvement_type);
+        let mut sites = Vec::new();
+        for (improvement_type, handle) in improvement_types {
+            let Some(improvement_type_data) = self.improvement_types_data.get(handle) else {
+                continue;
+            };
+            let targets: Vec<_> = match improvement_type_data.placement() {
+                Placement::Tile => vec![(tile.tile_entity(), String::new())],
+                Placement::Connection => coordinates
+                    .neighbours()
+                    .into_iter()
+                    .zip(DIRECTIONS)
+                    .filter_map(|(neighbour, direction)| {
+                        let connection = self
+                            .hexagonal_map
+                            .connection_between(coordinates, neighbour)?;
+                        let connection_type = self.connection_types.get(connection).ok().and_then(
+                            |connection_type| {
+                                self.connection_types_data
+                                    .get(connection_type.connection_type_data())
+                            },
+                        )?;
+                        Some((
+                            connection,
+                            format!(
+                                " across the {} to the {direction}",
+                                connection_type.display_name()
+                            ),
+                        ))
+                    })
+                    .collect(),
+            };
+            sites.extend(
+                targets
+                    .into_iter()
+                    .map(|(target, location)| ConstructionSite {
+                        target,
+                        improvement_type: improvement_type.clone(),
+                        label: format!(
+                            "{}{location} ({} production)",
+                            improvement_type_data.display_name(),
+                            improvement_type_data.cost()
+                        ),
+                        cost: u32::from(improvement_type_data.cost()),
+                        already_built: self.already_built(target, improvement_type),
+                    }),
+            );
+        }
+        sites
+    }
+}
+
+/// A terrain event the player can order work on.
+struct TerrainOrder {
+    event: String,
+    label: String,
+    cost: u32,
+}
+
+/// Looks up the terrain events which change the tile type of a tile, like clearing a forest, and the work going on for them.
+#[derive(SystemParam)]
+pub(super) struct Terrain<'w, 's> {
+    hexagonal_map: Res<'w, HexagonalMap<Tile>>,
+    tiles: Query<'w, 's, (&'static TileType, Option<&'static TerrainWork>)>,
+    tile_types: Res<'w, TileTypes>,
+    tile_types_data: Res<'w, Assets<assets::TileType>>,
+}
+
+impl Terrain<'_, '_> {
+    fn tile(&self, coordinates: AxialCoordinates) -> Option<(&TileType, Option<&TerrainWork>)> {
+        self.hexagonal_map
+            .get(coordinates)
+            .and_then(|tile| self.tiles.get(tile.tile_entity()).ok())
+    }
+
+    /// The work going on on the tile at `coordinates`, if any.
+    fn work(&self, coordinates: AxialCoordinates) -> Option<&TerrainWork> {
+        self.tile(coordinates)
+            .and_then(|(_, terrain_work)| terrain_work)
+    }
+
+    /// The terrain events with a transition on the tile at `coordinates`, labelled with the tile type it turns into and what the work costs.
+    fn orders(&self, coordinates: AxialCoordinates) -> Vec<TerrainOrder> {
+        let Some(tile_type_data) = self
+            .tile(coordinates)
+            .and_then(|(tile_type, _)| self.tile_types_data.get(tile_type.tile_type_data()))
+        else {
+            return Vec::new();
+        };
+        tile_type_data
+            .transitions()
+            .iter()
+            .filter_map(|transition| {
+                let TransitionTrigger::Event(event) = transition.trigger() else {
+                    return None;
+                };
+                let tile_type = self
+                    .tile_types
+                    .get(transition.tile_type())
+                    .and_then(|handle| self.tile_types_data.get(handle))
+                    .map_or(transition.tile_type().as_str(), |tile_type| {
+                        tile_type.display_name()
+                    });
+                Some(TerrainOrder {
+                    event: event.clone(),
+                    label: format!(
+                        "{event}, turning it into {tile_type} ({} production, {} turns)",
+                        transition.cost(),
+                        transition.turns()
+                    ),
+                    cost: u32::from(transition.cost()),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Let the player order improvements on the tile of the selected units and work on its terrain, if their faction controls it.
+/// Working on the terrain takes the remaining movement points of the selected units on the tile.
+pub(super) fn tile_orders(
+    mut egui_context: Query<&mut EguiContext, With<PrimaryWindow>>,
+    selection: Res<Selection>,
+    active_faction: Res<ActiveFaction>,
+    units: Query<(&Unit, &AxialCoordinates, &Owner)>,
+    unit_types: Res<Assets<assets::UnitType>>,
+    tile_control: Res<TileControl>,
+    production: Res<Production>,
+    construction: Construction,
+    terrain: Terrain,
+    mut build_improvement_events: EventWriter<BuildImprovement>,
+    mut start_terrain_work_events: EventWriter<StartTerrainWork>,
+) {
+    let Some((_, coordinates, Owner(faction))) =
+        selection.0.first().and_then(|unit| units.get(*unit).ok())
+    else {
+        return;
+    };
+    if !active_faction.is(faction) || tile_control.controller(*coordinates) != Some(faction) {
+        return;
+    }
+    let Ok(mut egui_context) = egui_context.get_single_mut() else {
+        return;
+    };
+    let available_production = production.get(faction);
+    let can_work = units
+        .iter_many(&selection.0)
+        .any(|(unit, unit_coordinates, _)| {
+            unit_coordinates == coordinates
+                && unit_types
+                    .get(unit.unit_type_data())
+                    .is_some_and(|unit_type| unit.remaining_movement_points(unit_type) > 0)
+        });
+    let terrain_work = terrain.work(*coordinates);
+    EguiWindow::new("Orders")
+        .resizable(false)
+        .anchor(Align2::RIGHT_CENTER, [-8.0, 0.0])
+        .show(egui_context.get_mut(), |ui| {
+            ui.label(format!("{available_production} production"));
+            for site in construction.sites(*coordinates) {
+                let enabled = !site.already_built && site.cost <= available_production;
+                if ui.add_enabled(enabled, Button::new(site.label)).clicked() {
+                    build_improvement_events.send(BuildImprovement {
+                        target: site.target,
+                        improvement_type: site.improvement_type,
+                        faction: faction.clone(),
+                    });
+                }
+            }
+            if let Some(terrain_work) = terrain_work {
+                ui.label(format!(
+                    "{}: {} turns left",
+                    terrain_work.event(),
+                    terrain_work.remaining_turns()
+                ));
+            }
+            for order in terrain.orders(*coordinates) {
+                let enabled =
+                    can_work && terrain_work.is_none() && order.cost <= available_production;
+                if ui.add_enabled(enabled, Button::new(order.label)).clicked() {
+                    start_terrain_work_events.send(StartTerrainWork {
+                        coordinates: *coordinates,
+                        event: order.event,
+                        faction: faction.clone(),
+                        units: selection.0.clone(),
+                    });
+                }
+            }
+        });
+}
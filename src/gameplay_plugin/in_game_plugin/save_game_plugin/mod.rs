@@ -0,0 +1,25 @@
+use bevy::{
+    app::{Plugin, Update},
+    input::{common_conditions::input_just_pressed, keyboard::KeyCode},
+    prelude::{in_state, IntoSystemConfigs},
+};
+
+use crate::gameplay_plugin::GameplayStates;
+
+use self::systems::quicksave;
+
+mod systems;
+
+/// Writes the current game to a save file, which can be loaded from the main menu.
+pub(super) struct SaveGamePlugin;
+
+impl Plugin for SaveGamePlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(
+            Update,
+            quicksave
+                .run_if(input_just_pressed(KeyCode::F5))
+                .run_if(in_state(GameplayStates::InGame)),
+        );
+    }
+}
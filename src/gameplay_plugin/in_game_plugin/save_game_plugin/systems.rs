@@ -0,0 +1,274 @@
+use std::{fs, path::Path};
+
+use bevy::{
+    color::ColorToPacked,
+    ecs::system::SystemParam,
+    hierarchy::{Children, Parent},
+    log::{error, info},
+    prelude::{Entity, Query, Res, ResMut},
+};
+use ron::ser::{to_string_pretty, PrettyConfig};
+
+use crate::{
+    gameplay_plugin::{
+        components::{
+            AxialCoordinates, ConnectionType, Elevation, Formation, Improvement, MoveOrder, Owner,
+            TerrainDamage, TerrainWork, TextureVariant, TileType, Unit,
+        },
+        random::RngStream,
+        resources::{
+            ActiveFaction, Calendar, ContentRoot, Factions, GameRng, HexagonalMap, MapSeed,
+            Production, Supply, Tile, TileControl, Turn, Weather,
+        },
+        save_file::{
+            FormationId, SaveFile, SaveFileCalendar, SaveFileFaction, SaveFileFormation,
+            SaveFileImprovement, SaveFileRng, SaveFileSeason, SaveFileTerrainWork, SaveFileTile,
+            SaveFileTileConnection, SaveFileTileData, SaveFileUnit, SaveFileWeatherZone,
+        },
+    },
+    toast_plugin::Toasts,
+};
+
+/// The save file (relative to `assets`) the game is saved to when quicksaving.
+const QUICKSAVE_PATH: &str = "save_files/saves/quicksave";
+
+/// The factions with the production they stockpiled and the one whose turn it is.
+#[derive(SystemParam)]
+pub(super) struct SavedFactions<'w> {
+    factions: Res<'w, Factions>,
+    active_faction: Res<'w, ActiveFaction>,
+    production: Res<'w, Production>,
+}
+
+impl SavedFactions<'_> {
+    fn save_file_factions(&self) -> Vec<SaveFileFaction> {
+        self.factions
+            .0
+            .iter()
+            .map(|faction| {
+                let [red, green, blue] = faction.color().to_srgba().to_u8_array_no_alpha();
+                SaveFileFaction {
+                    id: faction.id().clone(),
+                    display_name: Some(faction.display_name().to_string()),
+                    color: (red, green, blue),
+                    production: self.production.get(faction.id()),
+                }
+            })
+            .collect()
+    }
+}
+
+/// The units and the formations they are organised in.
+#[derive(SystemParam)]
+pub(super) struct SavedOrderOfBattle<'w, 's> {
+    units: Query<
+        'w,
+        's,
+        (
+            &'static Unit,
+            &'static Owner,
+            &'static AxialCoordinates,
+            Option<&'static MoveOrder>,
+            Option<&'static Parent>,
+        ),
+    >,
+    formations: Query<'w, 's, (&'static Formation, &'static Owner, Option<&'static Parent>)>,
+}
+
+impl SavedOrderOfBattle<'_, '_> {
+    /// The id of the formation which is the parent of an entity, if any.
+    fn formation_id(&self, parent: Option<&Parent>) -> Option<FormationId> {
+        parent
+            .and_then(|parent| self.formations.get(parent.get()).ok())
+            .map(|(formation, ..)| formation.id().clone())
+    }
+
+    /// The formations, sorted from the highest echelon to the lowest so every formation comes after its parent.
+    fn save_file_formations(&self) -> Vec<SaveFileFormation> {
+        let mut formations: Vec<_> = self
+            .formations
+            .iter()
+            .map(|(formation, Owner(owner), parent)| SaveFileFormation {
+                id: formation.id().clone(),
+                display_name: Some(formation.display_name().to_string()),
+                echelon: formation.echelon(),
+                owner: owner.clone(),
+                parent: self.formation_id(parent),
+            })
+            .collect();
+        formations.sort_by(|a, b| b.echelon.cmp(&a.echelon).then_with(|| a.id.cmp(&b.id)));
+        formations
+    }
+
+    fn save_file_units(&self) -> Vec<SaveFileUnit> {
+        self.units
+            .iter()
+            .map(
+                |(unit, Owner(owner), coordinates, move_order, parent)| SaveFileUnit {
+                    unit_type: unit.unit_type().clone(),
+                    owner: owner.clone(),
+                    coordinates: (coordinates.q(), coordinates.r()),
+                    formation: self.formation_id(parent),
+                    lost_health: unit.lost_health(),
+                    lost_organisation: unit.lost_organisation(),
+                    spent_movement_points: unit.spent_movement_points(),
+                    entrenchment: unit.entrenchment(),
+                    experience: unit.experience(),
+                    move_order: move_order
+                        .into_iter()
+                        .flat_map(|MoveOrder(path)| path)
+                        .map(|coordinates| (coordinates.q(), coordinates.r()))
+                        .collect(),
+                },
+            )
+            .collect()
+    }
+}
+
+pub(super) fn quicksave(
+    hexagonal_map: Res<HexagonalMap<Tile>>,
+    tiles: Query<(
+        &TileType,
+        &Elevation,
+        &TerrainDamage,
+        Option<&TerrainWork>,
+        Option<&TextureVariant>,
+    )>,
+    tile_connections: Query<&ConnectionType>,
+    content_root: Res<ContentRoot>,
+    saved_factions: SavedFactions,
+    tile_control: Res<TileControl>,
+    supply: Res<Supply>,
+    turn: Res<Turn>,
+    map_seed: Res<MapSeed>,
+    game_rng: Res<GameRng>,
+    calendar: Res<Calendar>,
+    weather: Res<Weather>,
+    children: Query<&Children>,
+    improvements: Query<&Improvement>,
+    order_of_battle: SavedOrderOfBattle,
+    mut toasts: ResMut<Toasts>,
+) {
+    let mut save_file_tiles = Vec::with_capacity(hexagonal_map.tiles().len());
+    for (tile, coordinates) in hexagonal_map.iter() {
+        let Ok((tile_type, elevation, terrain_damage, terrain_work, texture_variant)) =
+            tiles.get(tile.tile_entity())
+        else {
+            error!(
+                "BUG: The tile at {coordinates:?} has no tile type, elevation or terrain damage."
+            );
+            return;
+        };
+        let [tile_connection_right, tile_connection_lower_right, tile_connection_lower_left] = tile
+            .tile_connection_entities()
+            .map(|tile_connection_entity| SaveFileTileConnection {
+                connection_type: tile_connections
+                    .get(tile_connection_entity)
+                    .ok()
+                    .map(|connection_type| connection_type.connection_type().clone()),
+                improvements: save_file_improvements(
+                    tile_connection_entity,
+                    &children,
+                    &improvements,
+                ),
+            });
+        save_file_tiles.push(SaveFileTile {
+            tile_data: SaveFileTileData {
+                tile_type: tile_type.tile_type().clone(),
+                elevation: elevation.level(),
+                controller: tile_control.controller(coordinates).cloned(),
+                improvements: save_file_improvements(tile.tile_entity(), &children, &improvements),
+                texture_variant: texture_variant.map(|texture_variant| texture_variant.0),
+                weather_zone: weather.zone(coordinates).map(|zone| zone.id().clone()),
+                terrain_damage: terrain_damage.0,
+                terrain_work: terrain_work.map(|terrain_work| SaveFileTerrainWork {
+                    event: terrain_work.event().to_string(),
+                    remaining_turns: terrain_work.remaining_turns(),
+                }),
+                supply: supply
+                    .0
+                    .keys()
+                    .map(|faction| (faction.clone(), supply.get(faction, coordinates)))
+                    .filter(|(_, amount)| *amount > 0)
+                    .collect(),
+            },
+            tile_connection_right,
+            tile_connection_lower_right,
+            tile_connection_lower_left,
+        });
+    }
+    let save_file = SaveFile {
+        scenario: Some(content_root.0.clone()),
+        turn: turn.0,
+        seed: map_seed.0,
+        rng: Some(SaveFileRng {
+            seed: game_rng.seed(),
+            combat_draws: game_rng.draws(RngStream::Combat),
+            ai_draws: game_rng.draws(RngStream::Ai),
+            weather_draws: game_rng.draws(RngStream::Weather),
+        }),
+        calendar: SaveFileCalendar {
+            start_day: calendar.start_day(),
+            days_per_turn: calendar.days_per_turn(),
+        },
+        weather_zones: weather
+            .zones()
+            .iter()
+            .map(|zone| SaveFileWeatherZone {
+                id: zone.id().clone(),
+                seasons: zone
+                    .seasons()
+                    .iter()
+                    .map(|season| SaveFileSeason {
+                        start_day: season.start_day(),
+                        weather: season.weather().cloned(),
+                    })
+                    .collect(),
+            })
+            .collect(),
+        factions: saved_factions.save_file_factions(),
+        active_faction: saved_factions.active_faction.0.clone(),
+        formations: order_of_battle.save_file_formations(),
+        units: order_of_battle.save_file_units(),
+        tiles: save_file_tiles,
+    };
+
+    match write_save_file(QUICKSAVE_PATH, &save_file) {
+        Ok(()) => {
+            info!("Saved the game to {QUICKSAVE_PATH}");
+            toasts.info("Quicksaved");
+        }
+        Err(err) => {
+            error!("Failed to save the game to {QUICKSAVE_PATH}: {err}");
+            toasts.error(format!("Could not save the game: {err}"));
+        }
+    }
+}
+
+/// The improvements built or under construction on `entity`, which is a tile or a tile connection.
+fn save_file_improvements(
+    entity: Entity,
+    children: &Query<&Children>,
+    improvements: &Query<&Improvement>,
+) -> Vec<SaveFileImprovement> {
+    children
+        .get(entity)
+        .into_iter()
+        .flatten()
+        .filter_map(|child| improvements.get(*child).ok())
+        .map(|improvement| SaveFileImprovement {
+            improvement_type: improvement.improvement_type().clone(),
+            remaining_build_turns: improvement.remaining_build_turns(),
+            lost_durability: improvement.lost_durability(),
+        })
+        .collect()
+}
+
+/// Write `save_file` to the save file at `path` (relative to `assets`), replacing it if it exists.
+fn write_save_file(path: &str, save_file: &SaveFile) -> Result<(), String> {
+    let directory = Path::new("assets").join(path);
+    let game_state =
+        to_string_pretty(save_file, PrettyConfig::default()).map_err(|err| err.to_string())?;
+    fs::create_dir_all(&directory).map_err(|err| err.to_string())?;
+    fs::write(directory.join("game_state.ron"), game_state).map_err(|err| err.to_string())
+}
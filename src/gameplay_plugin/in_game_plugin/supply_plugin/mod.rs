@@ -0,0 +1,40 @@
+use bevy::{
+    app::{Plugin, Update},
+    input::{common_conditions::input_just_pressed, keyboard::KeyCode},
+    prelude::{in_state, IntoSystemConfigs, OnEnter},
+};
+
+use crate::{cleanup::Cleanup, gameplay_plugin::GameplayStates};
+
+use self::{
+    resources::SupplyOverlay,
+    systems::{
+        cut_captured_supply, draw_supply_overlay, supply_units, toggle_supply_overlay,
+        trace_initial_supply,
+    },
+};
+
+mod resources;
+mod systems;
+
+/// Traces the supply of every faction each turn, lets units out of supply suffer attrition and draws the supply overlay.
+pub(super) struct SupplyPlugin;
+
+impl Plugin for SupplyPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(OnEnter(GameplayStates::InGame), trace_initial_supply)
+            .add_systems(
+                Update,
+                (
+                    cut_captured_supply,
+                    supply_units,
+                    toggle_supply_overlay.run_if(input_just_pressed(KeyCode::F2)),
+                    draw_supply_overlay,
+                )
+                    .chain()
+                    .run_if(in_state(GameplayStates::InGame)),
+            );
+        app.init_resource::<SupplyOverlay>()
+            .reset_resource::<SupplyOverlay>(GameplayStates::InGame);
+    }
+}
@@ -0,0 +1,5 @@
+use bevy::prelude::Resource;
+
+/// Whether the supply of every faction is drawn onto the map.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SupplyOverlay(pub bool);
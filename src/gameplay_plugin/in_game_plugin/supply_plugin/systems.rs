@@ -0,0 +1,269 @@
+use bevy::{
+    asset::Assets,
+    color::Color,
+    ecs::system::SystemParam,
+    hierarchy::DespawnRecursiveExt,
+    math::Vec2,
+    prelude::{Commands, Entity, EventReader, Gizmos, Query, Res, ResMut, With},
+};
+
+use crate::{
+    gameplay_plugin::{
+        assets,
+        components::{AxialCoordinates, Improvement, Owner, TileType, Unit, TILE_CORNERS},
+        events::{TileCaptured, TurnEnded},
+        in_game_plugin::movement_plugin::MovementRules,
+        resources::{Faction, Factions, Supply, TileControl},
+        supply::{self, SUPPLY_LOSS_PER_STEP},
+    },
+    toast_plugin::Toasts,
+};
+
+use super::resources::SupplyOverlay;
+
+/// How far the overlay is drawn inside of a tile with the least and the most supply.
+const OVERLAY_INSET: (f32, f32) = (0.2, 0.7);
+/// Marks units that are out of supply while the overlay is shown.
+const OUT_OF_SUPPLY_COLOR: Color = Color::srgb(0.9, 0.1, 0.1);
+const OUT_OF_SUPPLY_RADIUS: f32 = 4.0;
+
+/// Looks up where the supply of a faction comes from and how it flows between tiles.
+#[derive(SystemParam)]
+pub(super) struct SupplyRules<'w, 's> {
+    movement_rules: MovementRules<'w, 's>,
+    tile_control: Res<'w, TileControl>,
+    tiles: Query<'w, 's, &'static TileType>,
+    tile_types: Res<'w, Assets<assets::TileType>>,
+    improvements: Query<'w, 's, &'static Improvement>,
+    improvement_types: Res<'w, Assets<assets::ImprovementType>>,
+}
+
+impl SupplyRules<'_, '_> {
+    fn tile_type(&self, coordinates: AxialCoordinates) -> Option<&assets::TileType> {
+        let tile = self.movement_rules.hexagonal_map().get(coordinates)?;
+        let tile_type = self.tiles.get(tile.tile_entity()).ok()?;
+        self.tile_types.get(tile_type.tile_type_data())
+    }
+
+    fn built_improvements(
+        &self,
+        coordinates: AxialCoordinates,
+    ) -> impl Iterator<Item = &assets::ImprovementType> {
+        self.movement_rules
+            .hexagonal_map()
+            .get(coordinates)
+            .into_iter()
+            .flat_map(|tile| self.movement_rules.children(tile.tile_entity()))
+            .filter_map(|child| self.improvements.get(child).ok())
+            .filter(|improvement| improvement.is_built())
+            .filter_map(|improvement| {
+                self.improvement_types
+                    .get(improvement.improvement_type_data())
+            })
+    }
+
+    /// The supply capacity of the tile type of the tile at `coordinates` plus the ones of the built improvements on it.
+    /// Supply of `faction` can't flow through tiles controlled by another faction.
+    fn capacity(&self, faction: &str, coordinates: AxialCoordinates) -> u8 {
+        if self
+            .tile_control
+            .controller(coordinates)
+            .is_some_and(|controller| controller != faction)
+        {
+            return 0;
+        }
+        let Some(tile_type) = self.tile_type(coordinates) else {
+            return 0;
+        };
+        self.built_improvements(coordinates)
+            .map(assets::ImprovementType::supply_capacity)
+            .fold(tile_type.supply_capacity(), u8::saturating_add)
+    }
+
+    /// The supply the tile at `coordinates` and the built improvements on it send out for `faction`, if it controls the tile.
+    fn source(&self, faction: &str, coordinates: AxialCoordinates) -> u8 {
+        if self
+            .tile_control
+            .controller(coordinates)
+            .map(String::as_str)
+            != Some(faction)
+        {
+            return 0;
+        }
+        let Some(tile_type) = self.tile_type(coordinates) else {
+            return 0;
+        };
+        self.built_improvements(coordinates)
+            .map(assets::ImprovementType::supply)
+            .fold(tile_type.supply(), u8::saturating_add)
+    }
+
+    /// Supply can't cross connections that block movement, and flows onto roads and across road connections without losing any.
+    fn loss(&self, from: AxialCoordinates, to: AxialCoordinates) -> Option<u8> {
+        let connection_type = self.movement_rules.connection_type_between(from, to);
+        if connection_type.is_some_and(assets::ConnectionType::blocks_movement) {
+            return None;
+        }
+        if connection_type.is_some_and(assets::ConnectionType::carries_supply)
+            || self
+                .tile_type(to)
+                .is_some_and(assets::TileType::carries_supply)
+        {
+            Some(0)
+        } else {
+            Some(SUPPLY_LOSS_PER_STEP)
+        }
+    }
+
+    /// Trace the supply of every faction, see [supply::trace_supply()].
+    pub fn trace(&self, factions: &Factions) -> Supply {
+        let hexagonal_map = self.movement_rules.hexagonal_map();
+        Supply(
+            factions
+                .0
+                .iter()
+                .map(|faction| {
+                    let sources = hexagonal_map
+                        .iter()
+                        .map(|(_, coordinates)| {
+                            (coordinates, self.source(faction.id(), coordinates))
+                        })
+                        .filter(|(_, amount)| *amount > 0);
+                    let supply = supply::trace_supply(
+                        hexagonal_map.radius(),
+                        sources,
+                        |coordinates| self.capacity(faction.id(), coordinates),
+                        |from, to| self.loss(from, to),
+                    );
+                    (faction.id().clone(), supply)
+                })
+                .collect(),
+        )
+    }
+
+    fn attrition_rate(&self, coordinates: AxialCoordinates) -> f32 {
+        self.tile_type(coordinates)
+            .map_or(0.0, assets::TileType::attrition_rate)
+    }
+}
+
+/// Trace the supply when a game starts that has none saved, like a scenario.
+pub(super) fn trace_initial_supply(
+    mut supply: ResMut<Supply>,
+    supply_rules: SupplyRules,
+    factions: Res<Factions>,
+) {
+    if supply.0.is_empty() {
+        *supply = supply_rules.trace(&factions);
+    }
+}
+
+/// A captured tile no longer carries the supply of the factions that lost it.
+pub(super) fn cut_captured_supply(
+    mut tile_captured_events: EventReader<TileCaptured>,
+    mut supply: ResMut<Supply>,
+) {
+    for TileCaptured {
+        coordinates,
+        controller,
+        ..
+    } in tile_captured_events.read()
+    {
+        supply.cut(*coordinates, controller);
+    }
+}
+
+/// Let units suffer attrition at the end of a turn, more so if they were out of supply unless they can forage, and trace the supply for the next turn.
+/// Units without health left are destroyed.
+pub(super) fn supply_units(
+    mut commands: Commands,
+    mut turn_ended_events: EventReader<TurnEnded>,
+    mut units: Query<(Entity, &mut Unit, &AxialCoordinates, &Owner)>,
+    unit_types: Res<Assets<assets::UnitType>>,
+    factions: Res<Factions>,
+    supply_rules: SupplyRules,
+    mut supply: ResMut<Supply>,
+    mut toasts: ResMut<Toasts>,
+) {
+    for _ in turn_ended_events.read() {
+        for (unit, mut unit_data, coordinates, Owner(faction)) in &mut units {
+            let Some(unit_type) = unit_types.get(unit_data.unit_type_data()) else {
+                continue;
+            };
+            let losses = supply::attrition(
+                unit_type.max_health(),
+                unit_type.max_organisation(),
+                supply_rules.attrition_rate(*coordinates),
+                supply.in_supply(faction, *coordinates)
+                    || unit_data.has_ability(unit_type, assets::Ability::Forage),
+            );
+            unit_data.take_losses(losses.health, losses.organisation);
+            if unit_data.health(unit_type) == 0 {
+                toasts.info(format!(
+                    "{} {} was lost to attrition",
+                    factions
+                        .get(faction)
+                        .map_or(faction.as_str(), Faction::display_name),
+                    unit_type.display_name()
+                ));
+                commands.entity(unit).despawn_recursive();
+            }
+        }
+        *supply = supply_rules.trace(&factions);
+    }
+}
+
+/// Toggle the supply overlay.
+pub(super) fn toggle_supply_overlay(mut supply_overlay: ResMut<SupplyOverlay>) {
+    supply_overlay.0 = !supply_overlay.0;
+}
+
+/// Draw a hexagon in the color of each faction on every tile its supply reaches, which is larger the more supply reaches the tile.
+/// Units out of supply are marked.
+pub(super) fn draw_supply_overlay(
+    mut gizmos: Gizmos,
+    supply_overlay: Res<SupplyOverlay>,
+    supply: Res<Supply>,
+    factions: Res<Factions>,
+    units: Query<(&AxialCoordinates, &Owner), With<Unit>>,
+) {
+    if !supply_overlay.0 {
+        return;
+    }
+    let most_supply = supply
+        .0
+        .values()
+        .flat_map(|supply| supply.iter().map(|(amount, _)| *amount))
+        .max()
+        .unwrap_or_default();
+    for (faction, faction_supply) in &supply.0 {
+        let Some(faction) = factions.get(faction) else {
+            continue;
+        };
+        for (amount, coordinates) in faction_supply.iter() {
+            if *amount == 0 {
+                continue;
+            }
+            let share = f32::from(*amount) / f32::from(most_supply);
+            let inset = OVERLAY_INSET.0 + (OVERLAY_INSET.1 - OVERLAY_INSET.0) * share;
+            let center = Vec2::from(coordinates);
+            for (index, corner) in TILE_CORNERS.iter().enumerate() {
+                let next_corner = TILE_CORNERS[(index + 1) % TILE_CORNERS.len()];
+                gizmos.line_2d(
+                    center + *corner * inset,
+                    center + next_corner * inset,
+                    faction.color(),
+                );
+            }
+        }
+    }
+    for (coordinates, Owner(faction)) in &units {
+        if !supply.in_supply(faction, *coordinates) {
+            gizmos.circle_2d(
+                Vec2::from(*coordinates),
+                OUT_OF_SUPPLY_RADIUS,
+                OUT_OF_SUPPLY_COLOR,
+            );
+        }
+    }
+}
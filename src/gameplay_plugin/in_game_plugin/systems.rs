@@ -1 +1,102 @@
-pub fn setup() {}
+use bevy::{
+    asset::{Assets, Handle},
+    prelude::{Commands, Query, Res, Without},
+    render::texture::Image,
+};
+
+use crate::gameplay_plugin::{
+    assets::{self, MovementCosts},
+    components::{AxialCoordinates, ConnectionType, Improvement, TileType, Unit},
+    resources::{
+        radius_and_amount_tiles::Radius, HexagonalMap, Tile, TileMovementCosts, Weather,
+        WeatherTypes,
+    },
+    weather,
+};
+
+/// Apply the textures of connections, improvements and units. Tiles are handled by the [super::tile_sprite_plugin::TileSpritePlugin].
+pub fn setup(
+    mut tile_connections: Query<(&ConnectionType, &mut Handle<Image>), Without<TileType>>,
+    mut improvements: Query<
+        (&Improvement, &mut Handle<Image>),
+        (Without<TileType>, Without<ConnectionType>),
+    >,
+    mut units: Query<
+        (&Unit, &mut Handle<Image>),
+        (
+            Without<TileType>,
+            Without<ConnectionType>,
+            Without<Improvement>,
+        ),
+    >,
+    connection_types: Res<Assets<assets::ConnectionType>>,
+    improvement_types: Res<Assets<assets::ImprovementType>>,
+    unit_types: Res<Assets<assets::UnitType>>,
+) {
+    for (connection_type, mut texture) in &mut tile_connections {
+        if let Some(connection_type_data) =
+            connection_types.get(connection_type.connection_type_data())
+        {
+            *texture = connection_type_data.texture().clone();
+        }
+    }
+    for (improvement, mut texture) in &mut improvements {
+        if let Some(improvement_type_data) =
+            improvement_types.get(improvement.improvement_type_data())
+        {
+            *texture = improvement_type_data.texture().clone();
+        }
+    }
+    for (unit, mut texture) in &mut units {
+        if let Some(unit_type_data) = unit_types.get(unit.unit_type_data()) {
+            *texture = unit_type_data.texture().clone();
+        }
+    }
+}
+
+pub fn cache_tile_movement_costs(
+    mut commands: Commands,
+    tiles: Query<(&TileType, &AxialCoordinates)>,
+    tile_types: Res<Assets<assets::TileType>>,
+    hexagonal_map: Res<HexagonalMap<Tile>>,
+    weather: Res<Weather>,
+    weather_types: Res<WeatherTypes>,
+    weather_type_assets: Res<Assets<assets::WeatherType>>,
+) {
+    commands.insert_resource(tile_movement_costs(
+        hexagonal_map.radius(),
+        tiles
+            .iter()
+            .map(|(tile_type, coordinates)| (tile_type, *coordinates)),
+        &tile_types,
+        &weather,
+        &weather_types,
+        &weather_type_assets,
+    ));
+}
+
+/// Look up the movement costs of every tile in its tile type and apply the current weather.
+pub(super) fn tile_movement_costs<'a>(
+    radius: Radius,
+    tiles: impl Iterator<Item = (&'a TileType, AxialCoordinates)>,
+    tile_types: &Assets<assets::TileType>,
+    weather: &Weather,
+    weather_types: &WeatherTypes,
+    weather_type_assets: &Assets<assets::WeatherType>,
+) -> TileMovementCosts {
+    let mut movement_costs = HexagonalMap::from_fn(radius, |_| MovementCosts::default());
+    for (tile_type, coordinates) in tiles {
+        let (Some(tile_movement_costs), Some(tile_type_data)) = (
+            movement_costs.get_mut(coordinates),
+            tile_types.get(tile_type.tile_type_data()),
+        ) else {
+            continue;
+        };
+        *tile_movement_costs = weather::movement_costs(
+            tile_type.tile_type(),
+            tile_type_data.movement_costs(),
+            weather.weather_type_at(coordinates, weather_types, weather_type_assets),
+        );
+    }
+    TileMovementCosts(movement_costs)
+}
@@ -0,0 +1,33 @@
+use bevy::{
+    app::{Plugin, Update},
+    prelude::{in_state, IntoSystemConfigs},
+};
+
+use crate::gameplay_plugin::GameplayStates;
+
+use self::systems::{
+    change_tile_types, continue_terrain_work, damage_terrain, start_terrain_work, transform_terrain,
+};
+
+mod systems;
+
+/// Changes the tile types of tiles through fighting and terrain events, as defined by the transitions of their tile types.
+/// Terrain events like clearing a forest are ordered by the player and take the production and turns their transition defines.
+pub(super) struct TerrainPlugin;
+
+impl Plugin for TerrainPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(
+            Update,
+            (
+                damage_terrain,
+                start_terrain_work,
+                continue_terrain_work,
+                transform_terrain,
+                change_tile_types,
+            )
+                .chain()
+                .run_if(in_state(GameplayStates::InGame)),
+        );
+    }
+}
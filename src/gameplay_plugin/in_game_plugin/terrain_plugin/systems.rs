@@ -0,0 +1,249 @@
+use bevy::{
+    asset::Assets,
+    core::Name,
+    log::warn,
+    prelude::{Commands, Entity, EventReader, EventWriter, Query, Res, ResMut},
+};
+
+use crate::{
+    gameplay_plugin::{
+        assets,
+        components::{
+            AxialCoordinates, Owner, TerrainDamage, TerrainWork, TextureVariant, TileType, Unit,
+        },
+        events::{ChangeTileType, StartTerrainWork, TerrainEvent, TileFoughtOver, TurnEnded},
+        resources::{
+            HexagonalMap, Production, Tile, TileMovementCosts, TileTypes, Weather, WeatherTypes,
+        },
+        weather,
+    },
+    toast_plugin::Toasts,
+};
+
+/// Add the damage of fighting to the tiles fought over and change the ones reaching a transition of their tile type.
+pub(super) fn damage_terrain(
+    mut tile_fought_over_events: EventReader<TileFoughtOver>,
+    mut change_tile_type_events: EventWriter<ChangeTileType>,
+    hexagonal_map: Res<HexagonalMap<Tile>>,
+    mut tiles: Query<(&TileType, &mut TerrainDamage)>,
+    tile_types: Res<Assets<assets::TileType>>,
+) {
+    for TileFoughtOver {
+        coordinates,
+        damage,
+        ..
+    } in tile_fought_over_events.read()
+    {
+        let Some(tile) = hexagonal_map.get(*coordinates) else {
+            continue;
+        };
+        let Ok((tile_type, mut terrain_damage)) = tiles.get_mut(tile.tile_entity()) else {
+            continue;
+        };
+        terrain_damage.0 = terrain_damage.0.saturating_add(u16::from(*damage));
+        if let Some(transition) = tile_types
+            .get(tile_type.tile_type_data())
+            .and_then(|tile_type_data| tile_type_data.transition_after_fighting(terrain_damage.0))
+        {
+            change_tile_type_events.send(ChangeTileType {
+                coordinates: *coordinates,
+                tile_type: transition.clone(),
+            });
+        }
+    }
+}
+
+/// Start the ordered work on terrain events, paid with the production of the ordering faction and the remaining movement points of its units on the tile.
+/// Work that takes no turns makes the terrain event happen right away.
+pub(super) fn start_terrain_work(
+    mut commands: Commands,
+    mut start_terrain_work_events: EventReader<StartTerrainWork>,
+    mut terrain_events: EventWriter<TerrainEvent>,
+    hexagonal_map: Res<HexagonalMap<Tile>>,
+    tiles: Query<(&TileType, Option<&TerrainWork>)>,
+    tile_types: Res<Assets<assets::TileType>>,
+    mut units: Query<(&mut Unit, &AxialCoordinates, &Owner)>,
+    unit_types: Res<Assets<assets::UnitType>>,
+    mut production: ResMut<Production>,
+) {
+    for StartTerrainWork {
+        coordinates,
+        event,
+        faction,
+        units: workers,
+    } in start_terrain_work_events.read()
+    {
+        let Some(tile_entity) = hexagonal_map.get(*coordinates).map(Tile::tile_entity) else {
+            continue;
+        };
+        let Ok((tile_type, terrain_work)) = tiles.get(tile_entity) else {
+            continue;
+        };
+        if terrain_work.is_some() {
+            warn!("There already is work going on at {coordinates:?}");
+            continue;
+        }
+        let Some(transition) = tile_types
+            .get(tile_type.tile_type_data())
+            .and_then(|tile_type_data| tile_type_data.event_transition(event))
+        else {
+            warn!("The terrain event `{event}` can not happen at {coordinates:?}");
+            continue;
+        };
+        let workers: Vec<Entity> = workers
+            .iter()
+            .copied()
+            .filter(|worker| {
+                units
+                    .get(*worker)
+                    .is_ok_and(|(unit, unit_coordinates, Owner(owner))| {
+                        unit_coordinates == coordinates
+                            && owner == faction
+                            && unit_types
+                                .get(unit.unit_type_data())
+                                .is_some_and(|unit_type| {
+                                    unit.remaining_movement_points(unit_type) > 0
+                                })
+                    })
+            })
+            .collect();
+        if workers.is_empty() {
+            warn!("No unit of `{faction}` at {coordinates:?} can work on `{event}`");
+            continue;
+        }
+        if !production.spend(faction, u32::from(transition.cost())) {
+            warn!("`{faction}` does not have the production to work on `{event}`");
+            continue;
+        }
+        for worker in workers {
+            if let Ok((mut unit, ..)) = units.get_mut(worker) {
+                unit.spend_movement_points(u8::MAX);
+            }
+        }
+        if transition.turns() == 0 {
+            terrain_events.send(TerrainEvent {
+                coordinates: *coordinates,
+                name: event.clone(),
+            });
+        } else {
+            commands
+                .entity(tile_entity)
+                .insert(TerrainWork::new(event.clone(), transition.turns()));
+        }
+    }
+}
+
+/// Continue the work on terrain events at the end of a turn and make the finished ones happen.
+pub(super) fn continue_terrain_work(
+    mut commands: Commands,
+    mut turn_ended_events: EventReader<TurnEnded>,
+    mut terrain_events: EventWriter<TerrainEvent>,
+    mut tiles: Query<(Entity, &AxialCoordinates, &mut TerrainWork)>,
+) {
+    for _ in turn_ended_events.read() {
+        for (tile_entity, coordinates, mut terrain_work) in &mut tiles {
+            if !terrain_work.work() {
+                continue;
+            }
+            terrain_events.send(TerrainEvent {
+                coordinates: *coordinates,
+                name: terrain_work.event().to_string(),
+            });
+            commands.entity(tile_entity).remove::<TerrainWork>();
+        }
+    }
+}
+
+/// Change the tiles whose tile type has a transition for a terrain event that happened on them.
+pub(super) fn transform_terrain(
+    mut terrain_events: EventReader<TerrainEvent>,
+    mut change_tile_type_events: EventWriter<ChangeTileType>,
+    hexagonal_map: Res<HexagonalMap<Tile>>,
+    tiles: Query<&TileType>,
+    tile_types: Res<Assets<assets::TileType>>,
+) {
+    for TerrainEvent { coordinates, name } in terrain_events.read() {
+        let Some(tile_type) = hexagonal_map
+            .get(*coordinates)
+            .and_then(|tile| tiles.get(tile.tile_entity()).ok())
+        else {
+            continue;
+        };
+        if let Some(transition) = tile_types
+            .get(tile_type.tile_type_data())
+            .and_then(|tile_type_data| tile_type_data.transition_on_event(name))
+        {
+            change_tile_type_events.send(ChangeTileType {
+                coordinates: *coordinates,
+                tile_type: transition.clone(),
+            });
+        }
+    }
+}
+
+/// Swap the tile type of tiles and update their movement costs. Their sprites are updated by the tile sprite plugin.
+pub(super) fn change_tile_types(
+    mut commands: Commands,
+    mut change_tile_type_events: EventReader<ChangeTileType>,
+    hexagonal_map: Res<HexagonalMap<Tile>>,
+    mut tiles: Query<(&mut TileType, &mut TerrainDamage, &mut Name)>,
+    tile_types: Res<TileTypes>,
+    tile_type_assets: Res<Assets<assets::TileType>>,
+    weather: Res<Weather>,
+    weather_types: Res<WeatherTypes>,
+    weather_type_assets: Res<Assets<assets::WeatherType>>,
+    mut tile_movement_costs_cache: ResMut<TileMovementCosts>,
+    mut toasts: ResMut<Toasts>,
+) {
+    for ChangeTileType {
+        coordinates,
+        tile_type: new_tile_type,
+    } in change_tile_type_events.read()
+    {
+        let Some(tile) = hexagonal_map.get(*coordinates) else {
+            continue;
+        };
+        let Ok((mut tile_type, mut terrain_damage, mut name)) = tiles.get_mut(tile.tile_entity())
+        else {
+            continue;
+        };
+        // Fighting may reach the same transition several times before it is applied.
+        if tile_type.tile_type() == new_tile_type {
+            continue;
+        }
+        let Some((new_tile_type_asset, new_tile_type_data)) =
+            tile_types.get(new_tile_type).and_then(|handle| {
+                tile_type_assets
+                    .get(handle)
+                    .map(|tile_type_data| (handle, tile_type_data))
+            })
+        else {
+            warn!("Can not turn the tile at {coordinates:?} into the unknown tile type `{new_tile_type}`");
+            continue;
+        };
+
+        if let Some(tile_movement_costs) = tile_movement_costs_cache.0.get_mut(*coordinates) {
+            *tile_movement_costs = weather::movement_costs(
+                new_tile_type,
+                new_tile_type_data.movement_costs(),
+                weather.weather_type_at(*coordinates, &weather_types, &weather_type_assets),
+            );
+        }
+        let previous_display_name = tile_type_assets.get(tile_type.tile_type_data()).map_or(
+            tile_type.tile_type().as_str(),
+            assets::TileType::display_name,
+        );
+        toasts.info(format!(
+            "{previous_display_name} turned into {}",
+            new_tile_type_data.display_name()
+        ));
+
+        *tile_type = TileType::new(new_tile_type.clone(), new_tile_type_asset.clone());
+        terrain_damage.0 = 0;
+        name.set(new_tile_type.clone());
+        // The texture variant was chosen for the previous tile type.
+        commands
+            .entity(tile.tile_entity())
+            .remove::<TextureVariant>();
+    }
+}
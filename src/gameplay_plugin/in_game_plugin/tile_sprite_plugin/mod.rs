@@ -0,0 +1,23 @@
+use bevy::{
+    app::{Plugin, Update},
+    prelude::{in_state, IntoSystemConfigs},
+};
+
+use crate::gameplay_plugin::GameplayStates;
+
+use self::systems::update_tile_sprites;
+
+mod systems;
+
+/// Chooses the sprite of every tile from its tile type, its neighbours and its texture variant.
+/// See [crate::gameplay_plugin::assets::autotile::Autotile] and [crate::gameplay_plugin::assets::texture_variants::TextureVariants].
+pub(super) struct TileSpritePlugin;
+
+impl Plugin for TileSpritePlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(
+            Update,
+            update_tile_sprites.run_if(in_state(GameplayStates::InGame)),
+        );
+    }
+}
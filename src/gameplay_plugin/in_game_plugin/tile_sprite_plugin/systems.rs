@@ -0,0 +1,134 @@
+use std::collections::HashSet;
+
+use bevy::{
+    asset::{AssetEvent, Assets, Handle},
+    prelude::{Changed, Commands, Entity, EventReader, Or, Query, RemovedComponents, Res, Without},
+    render::texture::Image,
+    sprite::TextureAtlas,
+};
+
+use crate::gameplay_plugin::{
+    assets::{self, neighbour_mask, Neighbour},
+    components::{AxialCoordinates, ConnectedTiles, ConnectionType, TextureVariant, TileType},
+    resources::{HexagonalMap, MapSeed, Tile},
+};
+
+/// Choose the texture and frame of every tile whose own or neighbouring tile or connection types changed.
+/// Everything counts as changed when the game starts, and all tiles are updated when a tile type is modified.
+pub(super) fn update_tile_sprites(
+    mut commands: Commands,
+    mut tile_type_events: EventReader<AssetEvent<assets::TileType>>,
+    changed_tiles: Query<&AxialCoordinates, Or<(Changed<TileType>, Changed<TextureVariant>)>>,
+    changed_tile_connections: Query<&ConnectedTiles, Changed<ConnectionType>>,
+    mut removed_connection_types: RemovedComponents<ConnectionType>,
+    all_connected_tiles: Query<&ConnectedTiles>,
+    mut tiles: Query<(
+        Entity,
+        &TileType,
+        &AxialCoordinates,
+        Option<&TextureVariant>,
+        &mut Handle<Image>,
+        Option<&mut TextureAtlas>,
+    )>,
+    tile_connections: Query<&ConnectionType, Without<TileType>>,
+    tile_types: Res<Assets<assets::TileType>>,
+    hexagonal_map: Res<HexagonalMap<Tile>>,
+    map_seed: Res<MapSeed>,
+) {
+    let tile_types_modified = tile_type_events
+        .read()
+        .any(|event| matches!(event, AssetEvent::Modified { .. }));
+
+    // A tile has to be updated if it or one of its neighbours changed, since autotiles depend on their neighbours.
+    let mut changed: HashSet<AxialCoordinates> = changed_tiles.iter().copied().collect();
+    let connected_tiles = changed_tile_connections.iter().chain(
+        removed_connection_types
+            .read()
+            .filter_map(|tile_connection| all_connected_tiles.get(tile_connection).ok()),
+    );
+    for ConnectedTiles(tile, other_tile) in connected_tiles {
+        for tile in [tile, other_tile] {
+            if let Ok((_, _, coordinates, ..)) = tiles.get(*tile) {
+                changed.insert(*coordinates);
+            }
+        }
+    }
+    let needs_update = |coordinates: &AxialCoordinates| {
+        tile_types_modified
+            || changed.contains(coordinates)
+            || coordinates
+                .neighbours()
+                .iter()
+                .any(|neighbour| changed.contains(neighbour))
+    };
+
+    let tile_type_at = |coordinates: AxialCoordinates| {
+        let tile = hexagonal_map.get(coordinates)?;
+        tiles
+            .get(tile.tile_entity())
+            .ok()
+            .map(|(_, tile_type, ..)| tile_type.tile_type())
+    };
+    let mut sprites = Vec::new();
+    for (tile_entity, tile_type, coordinates, texture_variant, ..) in &tiles {
+        if !needs_update(coordinates) {
+            continue;
+        }
+        let Some(tile_type_data) = tile_types.get(tile_type.tile_type_data()) else {
+            continue;
+        };
+        let Some(autotile) = tile_type_data.autotile() else {
+            let variants = tile_type_data.texture_variants();
+            let variant = texture_variant
+                .map(|texture_variant| usize::from(texture_variant.0))
+                .filter(|variant| *variant < variants.count())
+                .unwrap_or_else(|| variants.pick(*coordinates, map_seed.0));
+            let sprite = variants
+                .get(variant, tile_type_data.texture())
+                .expect("BUG: The variant is either picked from the variants or checked to exist.");
+            sprites.push((tile_entity, sprite));
+            continue;
+        };
+        let joined = coordinates.neighbours().map(|neighbour_coordinates| {
+            let neighbour = match tile_type_at(neighbour_coordinates) {
+                Some(neighbour_tile_type) => Neighbour::Tile {
+                    tile_type: neighbour_tile_type,
+                    connection_type: hexagonal_map
+                        .connection_between(*coordinates, neighbour_coordinates)
+                        .and_then(|tile_connection| tile_connections.get(tile_connection).ok())
+                        .map(ConnectionType::connection_type),
+                },
+                None => Neighbour::MapEdge,
+            };
+            autotile.joins(tile_type.tile_type(), neighbour)
+        });
+        let frame = TextureAtlas {
+            layout: autotile.layout().clone(),
+            index: autotile.index(neighbour_mask(joined)),
+        };
+        sprites.push((tile_entity, (tile_type_data.texture().clone(), Some(frame))));
+    }
+
+    for (tile_entity, (new_texture, frame)) in sprites {
+        let Ok((.., mut texture, texture_atlas)) = tiles.get_mut(tile_entity) else {
+            continue;
+        };
+        if *texture != new_texture {
+            *texture = new_texture;
+        }
+        match (texture_atlas, frame) {
+            (Some(mut texture_atlas), Some(frame)) => {
+                if texture_atlas.layout != frame.layout || texture_atlas.index != frame.index {
+                    *texture_atlas = frame;
+                }
+            }
+            (None, Some(frame)) => {
+                commands.entity(tile_entity).insert(frame);
+            }
+            (Some(_), None) => {
+                commands.entity(tile_entity).remove::<TextureAtlas>();
+            }
+            (None, None) => {}
+        }
+    }
+}
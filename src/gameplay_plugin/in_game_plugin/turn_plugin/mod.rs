@@ -0,0 +1,19 @@
+use bevy::{
+    app::{Plugin, Update},
+    prelude::{in_state, IntoSystemConfigs},
+};
+
+use crate::gameplay_plugin::GameplayStates;
+
+use self::systems::turn_panel;
+
+mod systems;
+
+/// Shows the current turn and the faction whose turn it is and lets the player pass the turn on to the next faction.
+pub(super) struct TurnPlugin;
+
+impl Plugin for TurnPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Update, turn_panel.run_if(in_state(GameplayStates::InGame)));
+    }
+}
@@ -0,0 +1,67 @@
+use bevy::{
+    color::ColorToPacked,
+    input::{keyboard::KeyCode, ButtonInput},
+    prelude::{EventWriter, Query, Res, ResMut, With},
+};
+use bevy_egui::{
+    egui::{Align2, Color32, Window},
+    EguiContext,
+};
+use bevy_window::PrimaryWindow;
+
+use crate::gameplay_plugin::{
+    events::TurnEnded,
+    resources::{ActiveFaction, Calendar, Factions, Turn},
+    weather,
+};
+
+/// Show the current turn, its date and the faction whose turn it is. Pass the turn on to the next faction when the button or enter is pressed.
+/// The turn ends once the last faction passed it on.
+pub(super) fn turn_panel(
+    mut egui_context: Query<&mut EguiContext, With<PrimaryWindow>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut turn: ResMut<Turn>,
+    calendar: Res<Calendar>,
+    factions: Res<Factions>,
+    mut active_faction: ResMut<ActiveFaction>,
+    mut turn_ended_events: EventWriter<TurnEnded>,
+) {
+    let mut end_turn = keys.just_pressed(KeyCode::Enter);
+    if let Ok(mut egui_context) = egui_context.get_single_mut() {
+        Window::new("Turn")
+            .title_bar(false)
+            .resizable(false)
+            .anchor(Align2::RIGHT_TOP, [-8.0, 8.0])
+            .show(egui_context.get_mut(), |ui| {
+                let (day, month) = weather::date(calendar.day_of_year(turn.0));
+                ui.label(format!("Turn {}, {day} {month}", turn.0));
+                if let Some(faction) = active_faction
+                    .0
+                    .as_ref()
+                    .and_then(|faction| factions.get(faction))
+                {
+                    let [red, green, blue] = faction.color().to_srgba().to_u8_array_no_alpha();
+                    ui.colored_label(
+                        Color32::from_rgb(red, green, blue),
+                        format!("{} to move", faction.display_name()),
+                    );
+                }
+                end_turn |= ui.button("End turn").clicked();
+            });
+    }
+    if !end_turn {
+        return;
+    }
+    let next_faction = active_faction
+        .0
+        .as_ref()
+        .and_then(|faction| factions.after(faction));
+    match next_faction {
+        Some(next_faction) => active_faction.0 = Some(next_faction.id().clone()),
+        None => {
+            turn_ended_events.send(TurnEnded { turn: turn.0 });
+            turn.0 += 1;
+            active_faction.0 = factions.0.first().map(|faction| faction.id().clone());
+        }
+    }
+}
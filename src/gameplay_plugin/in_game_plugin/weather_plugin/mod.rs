@@ -0,0 +1,25 @@
+use bevy::{
+    app::{Plugin, Update},
+    prelude::{in_state, IntoSystemConfigs, OnEnter},
+};
+
+use crate::gameplay_plugin::GameplayStates;
+
+use self::systems::{change_weather, show_weather, spawn_weather_overlays};
+
+mod systems;
+
+/// Changes the weather with the seasons and draws it on top of the tiles.
+pub(super) struct WeatherPlugin;
+
+impl Plugin for WeatherPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(OnEnter(GameplayStates::InGame), spawn_weather_overlays)
+            .add_systems(
+                Update,
+                (change_weather, show_weather)
+                    .chain()
+                    .run_if(in_state(GameplayStates::InGame)),
+            );
+    }
+}
@@ -0,0 +1,110 @@
+use bevy::{
+    asset::{AssetEvent, Assets, Handle},
+    core::Name,
+    hierarchy::{BuildChildren, Parent},
+    math::Vec3,
+    prelude::{Commands, DetectChanges, Entity, EventReader, Query, Res, ResMut, With},
+    render::{texture::Image, view::Visibility},
+    sprite::SpriteBundle,
+    transform::components::Transform,
+};
+
+use crate::{
+    gameplay_plugin::{
+        assets,
+        components::{AxialCoordinates, TileType, WeatherOverlay},
+        events::TurnEnded,
+        in_game_plugin::systems::tile_movement_costs,
+        resources::{Calendar, HexagonalMap, Tile, TileMovementCosts, Weather, WeatherTypes},
+    },
+    toast_plugin::Toasts,
+};
+
+/// Weather overlays are drawn relative to their tile, above improvements.
+const OVERLAY_OFFSET: Vec3 = Vec3::new(0.0, 0.0, 2.0);
+
+pub(super) fn spawn_weather_overlays(mut commands: Commands, tiles: Query<Entity, With<TileType>>) {
+    for tile in &tiles {
+        commands
+            .spawn((
+                // The texture is defined by the weather type and applied once the tile has weather.
+                SpriteBundle {
+                    transform: Transform::from_translation(OVERLAY_OFFSET),
+                    visibility: Visibility::Hidden,
+                    ..Default::default()
+                },
+                WeatherOverlay,
+                Name::new("weather_overlay"),
+            ))
+            .set_parent(tile);
+    }
+}
+
+/// Change the weather to the one of the season of the new turn and update the movement costs.
+pub(super) fn change_weather(
+    mut turn_ended_events: EventReader<TurnEnded>,
+    calendar: Res<Calendar>,
+    mut weather: ResMut<Weather>,
+    weather_types: Res<WeatherTypes>,
+    weather_type_assets: Res<Assets<assets::WeatherType>>,
+    tiles: Query<(&TileType, &AxialCoordinates)>,
+    tile_types: Res<Assets<assets::TileType>>,
+    hexagonal_map: Res<HexagonalMap<Tile>>,
+    mut tile_movement_costs_cache: ResMut<TileMovementCosts>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let Some(TurnEnded { turn }) = turn_ended_events.read().last() else {
+        return;
+    };
+    let changed = weather.update(calendar.day_of_year(turn + 1));
+    if changed.is_empty() {
+        return;
+    }
+    for zone in changed {
+        let weather_type = zone
+            .weather()
+            .and_then(|weather_type| weather_types.get(weather_type))
+            .and_then(|weather_type| weather_type_assets.get(weather_type))
+            .map_or("Clear weather", assets::WeatherType::display_name);
+        toasts.info(format!("{weather_type} in {}", zone.id()));
+    }
+    *tile_movement_costs_cache = tile_movement_costs(
+        hexagonal_map.radius(),
+        tiles
+            .iter()
+            .map(|(tile_type, coordinates)| (tile_type, *coordinates)),
+        &tile_types,
+        &weather,
+        &weather_types,
+        &weather_type_assets,
+    );
+}
+
+/// Draw the overlay of the current weather on every tile when the weather or a weather type changes.
+pub(super) fn show_weather(
+    mut weather_type_events: EventReader<AssetEvent<assets::WeatherType>>,
+    weather: Res<Weather>,
+    weather_types: Res<WeatherTypes>,
+    weather_type_assets: Res<Assets<assets::WeatherType>>,
+    mut overlays: Query<(&Parent, &mut Handle<Image>, &mut Visibility), With<WeatherOverlay>>,
+    tiles: Query<&AxialCoordinates>,
+) {
+    let weather_types_modified = weather_type_events
+        .read()
+        .any(|event| matches!(event, AssetEvent::Modified { .. }));
+    if !weather.is_changed() && !weather_types_modified {
+        return;
+    }
+    for (tile, mut texture, mut visibility) in &mut overlays {
+        let weather_type = tiles.get(tile.get()).ok().and_then(|coordinates| {
+            weather.weather_type_at(*coordinates, &weather_types, &weather_type_assets)
+        });
+        match weather_type {
+            Some(weather_type) => {
+                *texture = weather_type.overlay().clone();
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
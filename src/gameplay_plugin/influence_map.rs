@@ -0,0 +1,259 @@
+use std::{collections::HashMap, hash::Hash};
+
+use bevy::{ecs::system::Resource, reflect::Reflect};
+
+use super::{
+    components::AxialCoordinates,
+    pathfinding::{movement_costs_from, MovementCost},
+    resources::{radius_and_amount_tiles::Radius, HexagonalMap},
+};
+
+/// Something that projects the strength of a faction onto the map, like a unit or a city.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfluenceSource<F> {
+    pub faction: F,
+    pub coordinates: AxialCoordinates,
+    pub strength: f32,
+}
+
+/// How fast influence fades the further it spreads from its source.
+/// The distance is measured in movement cost, so influence spreads further along roads than through forests.
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct InfluenceDecay {
+    /// The share of influence that is kept per point of movement cost (`0.0..=1.0`).
+    pub retained_per_movement_cost: f32,
+    /// Influence does not spread further than this.
+    pub max_movement_cost: MovementCost,
+}
+
+impl Default for InfluenceDecay {
+    fn default() -> Self {
+        Self {
+            retained_per_movement_cost: 0.7,
+            max_movement_cost: 8,
+        }
+    }
+}
+
+impl InfluenceDecay {
+    pub fn decay(&self, strength: f32, movement_cost: MovementCost) -> f32 {
+        strength * self.retained_per_movement_cost.powi(movement_cost as i32)
+    }
+}
+
+/// The influence of every faction on every tile of the map.
+#[derive(Debug, Clone)]
+pub struct InfluenceMap<F> {
+    radius: Radius,
+    influence: HashMap<F, HexagonalMap<f32>>,
+}
+
+impl<F: Copy + Eq + Hash> InfluenceMap<F> {
+    /// Spread the strength of all sources across a map of the given radius.
+    /// The influence of several sources of the same faction adds up.
+    ///
+    /// `movement_cost` returns the cost of stepping from the first to the second (adjacent) coordinates or `None` if that step is impossible.
+    pub fn new(
+        radius: Radius,
+        sources: impl IntoIterator<Item = InfluenceSource<F>>,
+        decay: InfluenceDecay,
+        mut movement_cost: impl FnMut(AxialCoordinates, AxialCoordinates) -> Option<MovementCost>,
+    ) -> Self {
+        let mut influence = HashMap::new();
+        for InfluenceSource {
+            faction,
+            coordinates,
+            strength,
+        } in sources
+        {
+            let costs = movement_costs_from(
+                radius,
+                [coordinates],
+                decay.max_movement_cost,
+                &mut movement_cost,
+            );
+            let faction_influence = influence
+                .entry(faction)
+                .or_insert_with(|| HexagonalMap::from_fn(radius, |_| 0.0));
+            for (cost, coordinates) in costs.iter() {
+                let Some(cost) = cost else {
+                    continue;
+                };
+                if let Some(tile_influence) = faction_influence.get_mut(coordinates) {
+                    *tile_influence += decay.decay(strength, *cost);
+                }
+            }
+        }
+        Self { radius, influence }
+    }
+
+    /// The influence of `faction` on the tile at `coordinates`.
+    pub fn influence(&self, faction: F, coordinates: AxialCoordinates) -> f32 {
+        self.influence
+            .get(&faction)
+            .and_then(|influence| influence.get(coordinates))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The summed influence of all factions except `faction` on the tile at `coordinates`.
+    pub fn hostile_influence(&self, faction: F, coordinates: AxialCoordinates) -> f32 {
+        self.influence
+            .iter()
+            .filter(|(other, _)| **other != faction)
+            .filter_map(|(_, influence)| influence.get(coordinates))
+            .sum()
+    }
+
+    /// Derive the layers used by the AI and the overlays from the perspective of `faction`.
+    pub fn layers(&self, faction: F) -> InfluenceLayers {
+        let friendly = HexagonalMap::from_fn(self.radius, |coordinates| {
+            self.influence(faction, coordinates)
+        });
+        let hostile = HexagonalMap::from_fn(self.radius, |coordinates| {
+            self.hostile_influence(faction, coordinates)
+        });
+        let at = |map: &HexagonalMap<f32>, coordinates| map.get(coordinates).copied();
+
+        let balance = HexagonalMap::from_fn(self.radius, |coordinates| {
+            at(&friendly, coordinates).unwrap_or_default()
+                - at(&hostile, coordinates).unwrap_or_default()
+        });
+        let tension = HexagonalMap::from_fn(self.radius, |coordinates| {
+            at(&friendly, coordinates).unwrap_or_default()
+                + at(&hostile, coordinates).unwrap_or_default()
+        });
+        let vulnerability = HexagonalMap::from_fn(self.radius, |coordinates| {
+            at(&tension, coordinates).unwrap_or_default()
+                - at(&balance, coordinates).unwrap_or_default().abs()
+        });
+        // A tile is part of the front line if it is dominated by one side while one of its neighbours is dominated by the other.
+        let frontline = HexagonalMap::from_fn(self.radius, |coordinates| {
+            let Some(own_balance) = at(&balance, coordinates) else {
+                return false;
+            };
+            own_balance != 0.0
+                && coordinates.neighbours().into_iter().any(|neighbour| {
+                    at(&balance, neighbour).is_some_and(|neighbour_balance| {
+                        neighbour_balance != 0.0
+                            && neighbour_balance.is_sign_positive()
+                                != own_balance.is_sign_positive()
+                    })
+                })
+        });
+
+        InfluenceLayers {
+            balance,
+            tension,
+            vulnerability,
+            frontline,
+        }
+    }
+}
+
+/// Layers derived from an [InfluenceMap] from the perspective of a single faction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfluenceLayers {
+    /// Friendly minus hostile influence. Positive values mean the tile is controlled by the faction.
+    pub balance: HexagonalMap<f32>,
+    /// Friendly plus hostile influence. High values mean a lot of strength is concentrated around the tile.
+    pub tension: HexagonalMap<f32>,
+    /// Tension minus the absolute balance. High values mean the tile is contested by roughly equal strength.
+    pub vulnerability: HexagonalMap<f32>,
+    /// Whether the tile is adjacent to a tile dominated by the other side.
+    pub frontline: HexagonalMap<bool>,
+}
+
+#[cfg(test)]
+mod tests_influence_map {
+    use crate::gameplay_plugin::{
+        components::AxialCoordinates, resources::radius_and_amount_tiles::Radius,
+    };
+
+    use super::{InfluenceDecay, InfluenceMap, InfluenceSource};
+
+    const DECAY: InfluenceDecay = InfluenceDecay {
+        retained_per_movement_cost: 0.5,
+        max_movement_cost: 8,
+    };
+
+    fn source(faction: u8, q: i32, r: i32, strength: f32) -> InfluenceSource<u8> {
+        InfluenceSource {
+            faction,
+            coordinates: AxialCoordinates::new(q, r),
+            strength,
+        }
+    }
+
+    #[test]
+    fn test_influence_decays_with_movement_cost() {
+        let influence_map =
+            InfluenceMap::new(Radius::Radius2, [source(0, 0, 0, 8.0)], DECAY, |_, _| {
+                Some(1)
+            });
+        assert_eq!(influence_map.influence(0, AxialCoordinates::new(0, 0)), 8.0);
+        assert_eq!(influence_map.influence(0, AxialCoordinates::new(1, 0)), 4.0);
+        assert_eq!(influence_map.influence(0, AxialCoordinates::new(2, 0)), 2.0);
+        assert_eq!(influence_map.influence(1, AxialCoordinates::new(0, 0)), 0.0);
+
+        // Stepping onto the right half of the map is twice as expensive.
+        let influence_map =
+            InfluenceMap::new(Radius::Radius2, [source(0, 0, 0, 8.0)], DECAY, |_, to| {
+                Some(if to.q() > 0 { 2 } else { 1 })
+            });
+        assert_eq!(influence_map.influence(0, AxialCoordinates::new(1, 0)), 2.0);
+        assert_eq!(
+            influence_map.influence(0, AxialCoordinates::new(-1, 0)),
+            4.0
+        );
+    }
+
+    #[test]
+    fn test_impassable_tiles_block_influence() {
+        let influence_map =
+            InfluenceMap::new(Radius::Radius1, [source(0, 0, 0, 8.0)], DECAY, |_, to| {
+                (to != AxialCoordinates::new(1, 0)).then_some(1)
+            });
+        assert_eq!(influence_map.influence(0, AxialCoordinates::new(1, 0)), 0.0);
+        // Reached by going around the center.
+        assert_eq!(
+            influence_map.influence(0, AxialCoordinates::new(1, -1)),
+            4.0
+        );
+    }
+
+    #[test]
+    fn test_layers() {
+        let influence_map = InfluenceMap::new(
+            Radius::Radius2,
+            [source(0, -2, 0, 8.0), source(1, 2, 0, 8.0)],
+            DECAY,
+            |_, _| Some(1),
+        );
+        let layers = influence_map.layers(0);
+        let center = AxialCoordinates::new(0, 0);
+
+        // The center is equally far away from both sources.
+        assert_eq!(layers.balance.get(center), Some(&0.0));
+        assert_eq!(layers.tension.get(center), Some(&4.0));
+        assert_eq!(layers.vulnerability.get(center), Some(&4.0));
+
+        assert_eq!(layers.frontline.get(center), Some(&false));
+        assert_eq!(
+            layers.frontline.get(AxialCoordinates::new(-1, 0)),
+            Some(&false)
+        );
+        assert_eq!(
+            layers.frontline.get(AxialCoordinates::new(-2, 0)),
+            Some(&false)
+        );
+        // The front line zigzags through the rows above and below the center.
+        for (q, r) in [(-1, 1), (0, 1), (0, -1), (1, -1)] {
+            assert_eq!(
+                layers.frontline.get(AxialCoordinates::new(q, r)),
+                Some(&true),
+                "{q}, {r}"
+            );
+        }
+    }
+}
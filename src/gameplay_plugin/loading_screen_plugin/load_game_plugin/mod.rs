@@ -1,9 +1,9 @@
 use bevy::{
     app::{Plugin, Update},
-    prelude::{in_state, resource_exists, IntoSystemConfigs, OnEnter},
+    prelude::{in_state, resource_exists, IntoSystemConfigs, OnEnter, Res},
 };
-use resources::LoadFromFileSuccessful;
-use systems::{check_if_loaded, load_from_file};
+use resources::{LoadFromFileSuccessful, LoadingErrors};
+use systems::{check_if_loaded, load_from_file, show_loading_errors};
 
 use crate::{cleanup::Cleanup, gameplay_plugin::GameplayStates};
 
@@ -17,10 +17,16 @@ impl Plugin for LoadGamePlugin {
         app.add_systems(OnEnter(GameplayStates::LoadingScreen), load_from_file)
             .add_systems(
                 Update,
-                check_if_loaded
-                    .run_if(in_state(GameplayStates::LoadingScreen))
-                    .run_if(resource_exists::<LoadFromFileSuccessful>),
+                (
+                    check_if_loaded
+                        .run_if(resource_exists::<LoadFromFileSuccessful>)
+                        .run_if(|loading_errors: Res<LoadingErrors>| loading_errors.0.is_empty()),
+                    show_loading_errors,
+                )
+                    .run_if(in_state(GameplayStates::LoadingScreen)),
             )
             .cleanup_resource::<LoadFromFileSuccessful>(GameplayStates::LoadingScreen);
+        app.init_resource::<LoadingErrors>()
+            .reset_resource::<LoadingErrors>(GameplayStates::LoadingScreen);
     }
 }
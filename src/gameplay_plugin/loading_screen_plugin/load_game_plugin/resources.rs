@@ -5,3 +5,8 @@ use bevy::{asset::UntypedHandle, prelude::Resource};
 pub struct LoadFromFileSuccessful {
     pub assets_to_load: Vec<UntypedHandle>,
 }
+
+/// Everything that went wrong while loading the save file and its assets.
+/// Shown on the loading screen, so they can be fixed without digging through the logs.
+#[derive(Resource, Default, Debug, Clone, PartialEq, Eq)]
+pub struct LoadingErrors(pub Vec<String>);
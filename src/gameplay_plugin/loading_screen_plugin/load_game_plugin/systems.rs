@@ -1,37 +1,434 @@
-use std::{fmt::Display, fs::File, io::Read, vec};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::Display,
+    fs::File,
+    io::{self, Read},
+    path::Path,
+    vec,
+};
 
 use crate::{
     gameplay_plugin::{
-        components::{TileConnectionEntity, TileEntity, TileType},
-        resources::{HexagonalMap, Tile},
-        save_file::{SaveFile, SaveFileTile, SaveFileTileConnection, SaveFileTileData},
+        assets::RonDiagnostic,
+        components::{
+            AxialCoordinates, ConnectedTiles, ConnectionType, Elevation, Formation, Improvement,
+            MoveOrder, Owner, TerrainDamage, TerrainWork, TextureVariant, TileType, Unit,
+        },
+        content_library::{self, ContentKind},
+        elevation, improvements, movement,
+        random::{self, RngStream},
+        resources::{
+            ActiveFaction, Calendar, ContentRoot, Faction, Factions, GameRng, HexagonalMap,
+            ImprovementTypes, MapSeed, Production, Supply, Tile, TileControl, TileTypes, Turn,
+            UnitTypes, Weather, WeatherTypes, WeatherZone,
+        },
+        save_file::{
+            Echelon, FactionId, FormationId, ImprovementTypePath, SaveFile, SaveFileCalendar,
+            SaveFileFaction, SaveFileFormation, SaveFileImprovement, SaveFileRng, SaveFileSeason,
+            SaveFileTerrainWork, SaveFileTile, SaveFileTileConnection, SaveFileTileData,
+            SaveFileUnit, SaveFileWeatherZone,
+        },
+        units,
+        weather::{Season, DAYS_PER_YEAR},
         GameplayStates,
     },
     resources::SaveFilePath,
     GameStates,
 };
 
-use super::resources::LoadFromFileSuccessful;
+use super::resources::{LoadFromFileSuccessful, LoadingErrors};
 use bevy::{
-    asset::{AssetServer, LoadState},
+    asset::{Asset, AssetServer, Handle, LoadState, RecursiveDependencyLoadState, UntypedHandle},
+    color::Color,
     core::Name,
+    hierarchy::BuildChildren,
     log::error,
-    math::Vec2,
-    prelude::{Commands, NextState, Res, ResMut, StateScoped, Transform},
-    sprite::SpriteBundle,
+    math::{Quat, Vec2},
+    prelude::{
+        Commands, Entity, NextState, Query, Res, ResMut, SpatialBundle, StateScoped, Transform,
+        With,
+    },
+    sprite::{Sprite, SpriteBundle},
 };
+use bevy_egui::{
+    egui::{Align2, RichText, ScrollArea, Window},
+    EguiContext,
+};
+use bevy_window::PrimaryWindow;
 use ron::de::from_bytes;
 
-/// Utility function to log an error and set the game state to MainMenu.
-fn handle_error(game_states: &mut ResMut<NextState<GameStates>>, path: &str, err: impl Display) {
-    error!("Failed to load save file at {path}: {err}");
-    game_states.set(GameStates::MainMenu);
+/// Utility function to log an error and show it on the loading screen.
+fn handle_error(loading_errors: &mut ResMut<LoadingErrors>, path: &str, err: impl Display) {
+    let message = format!("Failed to load save file at {path}: {err}");
+    error!("{message}");
+    loading_errors.0.push(message);
+}
+
+/// Place a connection overlay on the border between the tiles at `coordinates` and `neighbour`, rotated so that the texture of the border to the right neighbour fits every border.
+fn connection_transform(coordinates: AxialCoordinates, neighbour: AxialCoordinates) -> Transform {
+    let center = Vec2::from(coordinates);
+    let direction = Vec2::from(neighbour) - center;
+    Transform::from_translation((center + direction / 2.0).extend(1.0))
+        .with_rotation(Quat::from_rotation_z(direction.to_angle()))
+}
+
+/// Spawn `improvements` as children of the tile or tile connection `parent`.
+/// Improvements of types that don't exist are skipped and added to `unknown_improvement_types`.
+fn spawn_improvements(
+    commands: &mut Commands,
+    parent: Entity,
+    improvements: Vec<SaveFileImprovement>,
+    improvement_types: &ImprovementTypes,
+    unknown_improvement_types: &mut Vec<ImprovementTypePath>,
+) {
+    for SaveFileImprovement {
+        improvement_type,
+        remaining_build_turns,
+        lost_durability,
+    } in improvements
+    {
+        let Some(improvement_type_asset) = improvement_types.get(&improvement_type) else {
+            unknown_improvement_types.push(improvement_type);
+            continue;
+        };
+        let improvement = Improvement::new(
+            improvement_type.clone(),
+            improvement_type_asset.clone(),
+            remaining_build_turns,
+            lost_durability,
+        );
+        commands
+            .spawn((
+                // The texture is defined by the improvement type and applied once it is loaded.
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: improvements::tint(&improvement),
+                        ..Default::default()
+                    },
+                    transform: Transform::from_translation(improvements::OFFSET),
+                    ..Default::default()
+                },
+                improvement,
+                Name::new(improvement_type),
+            ))
+            .set_parent(parent);
+    }
+}
+
+/// The entity of the formation `parent`, which the formation `id` of `owner` and `echelon` is subordinate to.
+/// The parent has to be spawned already, belong to the same faction and be of a higher echelon.
+fn formation_parent(
+    formation_entities: &HashMap<FormationId, (Entity, FactionId, Echelon)>,
+    id: &str,
+    owner: &FactionId,
+    echelon: Echelon,
+    parent: &str,
+) -> Result<Entity, String> {
+    match formation_entities.get(parent) {
+        Some((_, parent_owner, _)) if parent_owner != owner => Err(format!(
+            "The formation `{id}` is subordinate to `{parent}` of another faction"
+        )),
+        Some((_, _, parent_echelon)) if *parent_echelon <= echelon => Err(format!(
+            "The formation `{id}` is subordinate to `{parent}`, which is not of a higher echelon"
+        )),
+        Some((parent, ..)) => Ok(*parent),
+        None => Err(format!(
+            "The formation `{id}` is subordinate to `{parent}`, which is not defined before it"
+        )),
+    }
+}
+
+/// Load every entry of `kind` available to the scenario at `content_root` by its id, adding them to `assets_to_load`.
+fn load_available<A: Asset>(
+    content_root: &str,
+    kind: ContentKind,
+    asset_server: &AssetServer,
+    assets_to_load: &mut Vec<UntypedHandle>,
+) -> io::Result<HashMap<String, Handle<A>>> {
+    let paths = content_library::available(content_root, kind)?;
+    Ok(paths
+        .into_iter()
+        .map(|(id, path)| {
+            let asset = asset_server.load(path);
+            assets_to_load.push(asset.clone().untyped());
+            (id, asset)
+        })
+        .collect())
+}
+
+/// Load every unit, improvement, weather and tile type available to the scenario at `content_root`.
+/// Units are spawned by referencing their unit type, while improvements can be built, the weather can change and tiles can change their tile type without waiting for their type to load.
+fn load_content_types(
+    content_root: &str,
+    asset_server: &AssetServer,
+    assets_to_load: &mut Vec<UntypedHandle>,
+) -> io::Result<(UnitTypes, ImprovementTypes, WeatherTypes, TileTypes)> {
+    Ok((
+        UnitTypes(load_available(
+            content_root,
+            ContentKind::UnitType,
+            asset_server,
+            assets_to_load,
+        )?),
+        ImprovementTypes(load_available(
+            content_root,
+            ContentKind::ImprovementType,
+            asset_server,
+            assets_to_load,
+        )?),
+        WeatherTypes(load_available(
+            content_root,
+            ContentKind::WeatherType,
+            asset_server,
+            assets_to_load,
+        )?),
+        TileTypes(load_available(
+            content_root,
+            ContentKind::TileType,
+            asset_server,
+            assets_to_load,
+        )?),
+    ))
+}
+
+/// The factions with the production they stockpiled and the one whose turn it is, which defaults to the first one.
+fn load_factions(
+    factions: Vec<SaveFileFaction>,
+    active_faction: Option<FactionId>,
+) -> Result<(Factions, ActiveFaction, Production), String> {
+    let mut faction_resources = Vec::with_capacity(factions.len());
+    let mut production = Production::default();
+    for SaveFileFaction {
+        id,
+        display_name,
+        color: (red, green, blue),
+        production: stockpiled_production,
+    } in factions
+    {
+        if faction_resources
+            .iter()
+            .any(|faction: &Faction| *faction.id() == id)
+        {
+            return Err(format!("The faction `{id}` is defined more than once"));
+        }
+        let display_name = display_name.unwrap_or_else(|| id.clone());
+        production.gain(&id, stockpiled_production);
+        faction_resources.push(Faction::new(
+            id,
+            display_name,
+            Color::srgb_u8(red, green, blue),
+        ));
+    }
+    let factions = Factions(faction_resources);
+    let active_faction = match active_faction {
+        Some(active_faction) if factions.get(&active_faction).is_none() => {
+            return Err(format!(
+                "The active faction `{active_faction}` is not defined"
+            ));
+        }
+        Some(active_faction) => ActiveFaction(Some(active_faction)),
+        None => ActiveFaction(factions.0.first().map(|faction| faction.id().clone())),
+    };
+    Ok((factions, active_faction, production))
+}
+
+/// The weather zones with their seasons, whose weather has to be one of the `weather_types`.
+fn load_weather_zones(
+    weather_zones: Vec<SaveFileWeatherZone>,
+    weather_types: &WeatherTypes,
+) -> Result<Vec<WeatherZone>, String> {
+    let mut weather_zone_resources = Vec::with_capacity(weather_zones.len());
+    for SaveFileWeatherZone { id, seasons } in weather_zones {
+        if weather_zone_resources
+            .iter()
+            .any(|zone: &WeatherZone| *zone.id() == id)
+        {
+            return Err(format!("The weather zone `{id}` is defined more than once"));
+        }
+        let mut season_resources = Vec::with_capacity(seasons.len());
+        for SaveFileSeason { start_day, weather } in seasons {
+            if start_day >= DAYS_PER_YEAR {
+                return Err(format!("A season of the weather zone `{id}` starts at day {start_day}, but a year only has {DAYS_PER_YEAR} days"));
+            }
+            if let Some(weather) = weather
+                .as_ref()
+                .filter(|weather| weather_types.get(weather).is_none())
+            {
+                return Err(format!("The weather type `{weather}` does not exist"));
+            }
+            season_resources.push(Season::new(start_day, weather));
+        }
+        weather_zone_resources.push(WeatherZone::new(id, season_resources));
+    }
+    Ok(weather_zone_resources)
+}
+
+/// Spawn the order of battle.
+///
+/// ## Returns
+/// The entity, faction and echelon of every formation by its id.
+fn spawn_formations(
+    commands: &mut Commands,
+    formations: Vec<SaveFileFormation>,
+    factions: &Factions,
+) -> Result<HashMap<FormationId, (Entity, FactionId, Echelon)>, String> {
+    // Formations are listed before their subordinates, so the formation a formation is subordinate to has already been spawned.
+    let mut formation_entities: HashMap<FormationId, (Entity, FactionId, Echelon)> = HashMap::new();
+    for SaveFileFormation {
+        id,
+        display_name,
+        echelon,
+        owner,
+        parent,
+    } in formations
+    {
+        if formation_entities.contains_key(&id) {
+            return Err(format!("The formation `{id}` is defined more than once"));
+        }
+        if factions.get(&owner).is_none() {
+            return Err(format!(
+                "The formation `{id}` belongs to the unknown faction `{owner}`"
+            ));
+        }
+        let parent = parent
+            .map(|parent| formation_parent(&formation_entities, &id, &owner, echelon, &parent))
+            .transpose()?;
+        let display_name = display_name.unwrap_or_else(|| id.clone());
+        let mut formation = commands.spawn((
+            // Units are drawn relative to their formation, so it stays at the origin.
+            SpatialBundle::default(),
+            Formation::new(id.clone(), display_name, echelon),
+            Owner(owner.clone()),
+            Name::new(id.clone()),
+        ));
+        // Subordinate formations are despawned with their parent.
+        match parent {
+            Some(parent) => formation.set_parent(parent),
+            None => formation.insert(StateScoped(GameStates::Gameplay)),
+        };
+        formation_entities.insert(id, (formation.id(), owner, echelon));
+    }
+    Ok(formation_entities)
+}
+
+/// Check that no tile holds more units than the stacking limit or units of different factions.
+fn check_stacks(units: &[SaveFileUnit]) -> Result<(), String> {
+    // Units of different factions never share a tile, since the ones moving in would have attacked.
+    let mut stacks: HashMap<AxialCoordinates, (&FactionId, usize)> = HashMap::new();
+    for unit in units {
+        let (q, r) = unit.coordinates;
+        let coordinates = AxialCoordinates::new(q, r);
+        let (owner, stack) = stacks.entry(coordinates).or_insert((&unit.owner, 0));
+        if *owner != &unit.owner {
+            return Err(format!(
+                "Units of `{owner}` and `{}` stand on the tile at {coordinates:?}",
+                unit.owner
+            ));
+        }
+        *stack += 1;
+        if *stack > movement::STACKING_LIMIT {
+            return Err(format!(
+                "More than {} units of `{}` stand on the tile at {coordinates:?}",
+                movement::STACKING_LIMIT,
+                unit.owner
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Spawn the units onto the map, as children of their formation if they belong to one.
+fn spawn_units(
+    commands: &mut Commands,
+    units: Vec<SaveFileUnit>,
+    hexagonal_map: &HexagonalMap<Tile>,
+    unit_types: &UnitTypes,
+    factions: &Factions,
+    formation_entities: &HashMap<FormationId, (Entity, FactionId, Echelon)>,
+) -> Result<(), String> {
+    check_stacks(&units)?;
+    for SaveFileUnit {
+        unit_type,
+        owner,
+        coordinates: (q, r),
+        formation,
+        lost_health,
+        lost_organisation,
+        spent_movement_points,
+        entrenchment,
+        experience,
+        move_order,
+    } in units
+    {
+        let coordinates = AxialCoordinates::new(q, r);
+        if hexagonal_map.get(coordinates).is_none() {
+            return Err(format!("The unit at {coordinates:?} is outside of the map"));
+        }
+        let Some(unit_type_asset) = unit_types.get(&unit_type) else {
+            return Err(format!("The unit type `{unit_type}` does not exist"));
+        };
+        let Some(faction) = factions.get(&owner) else {
+            return Err(format!(
+                "The unit at {coordinates:?} belongs to the unknown faction `{owner}`"
+            ));
+        };
+        let formation = formation
+            .map(|formation| match formation_entities.get(&formation) {
+                Some((formation, formation_owner, _)) if *formation_owner == owner => {
+                    Ok(*formation)
+                }
+                Some(_) => Err(format!(
+                    "The unit at {coordinates:?} belongs to the formation `{formation}` of another faction"
+                )),
+                None => Err(format!(
+                    "The unit at {coordinates:?} belongs to the unknown formation `{formation}`"
+                )),
+            })
+            .transpose()?;
+        let mut unit = commands.spawn((
+            // The texture is defined by the unit type and applied once it is loaded.
+            SpriteBundle {
+                sprite: Sprite {
+                    color: units::tint(faction),
+                    ..Default::default()
+                },
+                transform: units::transform(coordinates),
+                ..Default::default()
+            },
+            Unit::new(
+                unit_type.clone(),
+                unit_type_asset.clone(),
+                lost_health,
+                lost_organisation,
+                spent_movement_points,
+                entrenchment,
+                experience,
+            ),
+            Owner(owner),
+            coordinates,
+            Name::new(unit_type),
+        ));
+        // Units of a formation are despawned with it.
+        match formation {
+            Some(formation) => unit.set_parent(formation),
+            None => unit.insert(StateScoped(GameStates::Gameplay)),
+        };
+        if !move_order.is_empty() {
+            unit.insert(MoveOrder(
+                move_order
+                    .into_iter()
+                    .map(|(q, r)| AxialCoordinates::new(q, r))
+                    .collect(),
+            ));
+        }
+    }
+    Ok(())
 }
 
 pub fn load_from_file(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    mut game_states: ResMut<NextState<GameStates>>,
+    mut loading_errors: ResMut<LoadingErrors>,
     save_file_path: Res<SaveFilePath>,
 ) {
     // Read the file and parse it.
@@ -41,21 +438,78 @@ pub fn load_from_file(
     let mut file = match File::open(&game_state_path) {
         Ok(file) => file,
         Err(err) => {
-            handle_error(&mut game_states, &game_state_path, err);
+            handle_error(&mut loading_errors, &game_state_path, err);
             return;
         }
     };
     if let Err(err) = file.read_to_end(&mut bytes) {
-        handle_error(&mut game_states, &game_state_path, err);
+        handle_error(&mut loading_errors, &game_state_path, err);
         return;
     };
-    let SaveFile { tiles } = match from_bytes::<SaveFile>(&bytes) {
+    let SaveFile {
+        scenario,
+        turn,
+        seed,
+        rng,
+        calendar,
+        weather_zones,
+        factions,
+        active_faction,
+        formations,
+        units,
+        tiles,
+    } = match from_bytes::<SaveFile>(&bytes) {
         Ok(value) => value,
         Err(err) => {
-            handle_error(&mut game_states, &game_state_path, err);
+            handle_error(
+                &mut loading_errors,
+                &game_state_path,
+                RonDiagnostic::new(Path::new(&game_state_path), &bytes, err),
+            );
             return;
         }
     };
+    // Content is resolved from the scenario, even if the game was saved elsewhere.
+    let content_root = scenario.unwrap_or_else(|| path.to_string());
+    let game_rng = match rng {
+        Some(SaveFileRng {
+            seed,
+            combat_draws,
+            ai_draws,
+            weather_draws,
+        }) => {
+            let mut draws = [0; RngStream::COUNT];
+            draws[RngStream::Combat.index()] = combat_draws;
+            draws[RngStream::Ai.index()] = ai_draws;
+            draws[RngStream::Weather.index()] = weather_draws;
+            GameRng::new(seed, draws)
+        }
+        None => GameRng::new(random::random_seed(), [0; RngStream::COUNT]),
+    };
+
+    let (factions, active_faction, production) = match load_factions(factions, active_faction) {
+        Ok(factions) => factions,
+        Err(err) => {
+            handle_error(&mut loading_errors, &game_state_path, err);
+            return;
+        }
+    };
+
+    let SaveFileCalendar {
+        start_day,
+        days_per_turn,
+    } = calendar;
+    if start_day >= DAYS_PER_YEAR {
+        handle_error(
+            &mut loading_errors,
+            &game_state_path,
+            format!(
+                "The calendar starts at day {start_day}, but a year only has {DAYS_PER_YEAR} days"
+            ),
+        );
+        return;
+    }
+    let calendar = Calendar::new(start_day, days_per_turn);
 
     // This will be converted into the final hexagonal map resource later on.
     let mut tiles_and_connection_entities = Vec::with_capacity(tiles.capacity());
@@ -66,14 +520,37 @@ pub fn load_from_file(
         Ok(val) => val,
         Err(err) => {
             handle_error(
-                &mut game_states,
+                &mut loading_errors,
                 &game_state_path,
                 format!("The length of `tiles` was incorrect: {err}"),
             );
             return;
         }
     };
+    let mut controllers = Vec::with_capacity(tiles_and_connection_entities.capacity());
+    let mut tile_supply = Vec::with_capacity(tiles_and_connection_entities.capacity());
     let mut assets_to_load = vec![];
+    let (unit_types, improvement_types, weather_types, tile_types) =
+        match load_content_types(&content_root, &asset_server, &mut assets_to_load) {
+            Ok(content_types) => content_types,
+            Err(err) => {
+                handle_error(&mut loading_errors, &game_state_path, err);
+                return;
+            }
+        };
+    let mut unknown_improvement_types = Vec::new();
+
+    let weather_zone_resources = match load_weather_zones(weather_zones, &weather_types) {
+        Ok(weather_zones) => weather_zones,
+        Err(err) => {
+            handle_error(&mut loading_errors, &game_state_path, err);
+            return;
+        }
+    };
+    let mut tile_weather_zones = Vec::with_capacity(tiles_and_connection_entities.capacity());
+
+    // Many tiles share a connection type, so each one is only resolved once.
+    let mut connection_type_paths = HashMap::new();
     for (
         SaveFileTile {
             tile_data,
@@ -85,46 +562,177 @@ pub fn load_from_file(
     ) in temporary_hexagonal_map.iter_with_coordinates()
     {
         // Instance the tile entity.
-        let SaveFileTileData { tile_type } = tile_data;
-        let tile_type_asset =
-            asset_server.load(format!("{path}/tile_types/{tile_type}/tile_type.ron"));
-        assets_to_load.push(tile_type_asset.clone().untyped());
-        let texture_asset = asset_server.load(format!("{path}/tile_types/{tile_type}/texture.png"));
-        assets_to_load.push(texture_asset.clone().untyped());
+        let SaveFileTileData {
+            tile_type,
+            elevation,
+            controller,
+            improvements,
+            texture_variant,
+            weather_zone,
+            terrain_damage,
+            terrain_work,
+            supply,
+        } = tile_data;
+        let weather_zone = match weather_zone {
+            Some(weather_zone) => match weather_zone_resources
+                .iter()
+                .position(|zone| *zone.id() == weather_zone)
+            {
+                Some(zone) => Some(zone),
+                None => {
+                    handle_error(
+                        &mut loading_errors,
+                        &game_state_path,
+                        format!("The tile at {coordinates:?} lies in the unknown weather zone `{weather_zone}`"),
+                    );
+                    return;
+                }
+            },
+            None => None,
+        };
+        tile_weather_zones.push(weather_zone);
+        if let Some(controller) = &controller {
+            if factions.get(controller).is_none() {
+                handle_error(
+                    &mut loading_errors,
+                    &game_state_path,
+                    format!("The tile at {coordinates:?} is controlled by the unknown faction `{controller}`"),
+                );
+                return;
+            }
+        }
+        controllers.push(controller);
+        if let Some(faction) = supply
+            .keys()
+            .find(|faction| factions.get(faction).is_none())
+        {
+            handle_error(
+                &mut loading_errors,
+                &game_state_path,
+                format!(
+                    "The tile at {coordinates:?} is supplied by the unknown faction `{faction}`"
+                ),
+            );
+            return;
+        }
+        tile_supply.push(supply);
+        let elevation = Elevation::new(elevation);
+        let Some(tile_type_asset) = tile_types.get(&tile_type).cloned() else {
+            handle_error(
+                &mut loading_errors,
+                &game_state_path,
+                format!("The tile type `{tile_type}` does not exist"),
+            );
+            return;
+        };
         let tile_entity = commands
             .spawn((
+                // The texture is defined by the tile type and applied once it is loaded.
                 SpriteBundle {
-                    texture: texture_asset,
+                    sprite: Sprite {
+                        color: elevation::tint(elevation),
+                        ..Default::default()
+                    },
                     transform: Transform::from_translation(Vec2::from(coordinates).extend(0.0)),
                     ..Default::default()
                 },
-                TileType::new(tile_type_asset),
+                TileType::new(tile_type.clone(), tile_type_asset),
+                TerrainDamage(terrain_damage),
+                elevation,
                 coordinates,
                 Name::new(tile_type),
                 StateScoped(GameStates::Gameplay),
             ))
             .id();
-
-        fn instance_tile_connection(
-            tile_connection: SaveFileTileConnection,
-            commands: &mut Commands<'_, '_>,
-        ) -> bevy::prelude::Entity {
-            let SaveFileTileConnection { temp_data } = tile_connection;
+        if let Some(texture_variant) = texture_variant {
+            commands
+                .entity(tile_entity)
+                .insert(TextureVariant(texture_variant));
+        }
+        if let Some(SaveFileTerrainWork {
+            event,
+            remaining_turns,
+        }) = terrain_work
+        {
             commands
-                .spawn((
-                    Name::new(format!("tile_connection_{}", temp_data.0)),
-                    temp_data,
-                    StateScoped(GameStates::Gameplay),
-                ))
-                .id()
+                .entity(tile_entity)
+                .insert(TerrainWork::new(event, remaining_turns));
         }
+        spawn_improvements(
+            &mut commands,
+            tile_entity,
+            improvements,
+            &improvement_types,
+            &mut unknown_improvement_types,
+        );
+
+        let [right, lower_right, lower_left, ..] = coordinates.neighbours();
+        let mut instance_tile_connection =
+            |tile_connection: SaveFileTileConnection, neighbour: AxialCoordinates| {
+                let SaveFileTileConnection {
+                    connection_type,
+                    improvements,
+                } = tile_connection;
+                let Some(connection_type) = connection_type else {
+                    // Plain borders are not drawn, but their improvements are.
+                    let tile_connection_entity = commands
+                        .spawn((
+                            SpatialBundle::from_transform(connection_transform(
+                                coordinates,
+                                neighbour,
+                            )),
+                            Name::new("tile_connection"),
+                            StateScoped(GameStates::Gameplay),
+                        ))
+                        .id();
+                    spawn_improvements(
+                        &mut commands,
+                        tile_connection_entity,
+                        improvements,
+                        &improvement_types,
+                        &mut unknown_improvement_types,
+                    );
+                    return tile_connection_entity;
+                };
+                let connection_type_path = connection_type_paths
+                    .entry(connection_type.clone())
+                    .or_insert_with(|| {
+                        content_library::resolve(
+                            &content_root,
+                            ContentKind::ConnectionType,
+                            &connection_type,
+                        )
+                    });
+                let connection_type_asset = asset_server.load(connection_type_path.clone());
+                assets_to_load.push(connection_type_asset.clone().untyped());
+                let tile_connection_entity = commands
+                    .spawn((
+                        // The overlay is drawn on the border between both tiles, above them.
+                        // The texture is defined by the connection type and applied once it is loaded.
+                        SpriteBundle {
+                            transform: connection_transform(coordinates, neighbour),
+                            ..Default::default()
+                        },
+                        ConnectionType::new(connection_type.clone(), connection_type_asset),
+                        Name::new(connection_type),
+                        StateScoped(GameStates::Gameplay),
+                    ))
+                    .id();
+                spawn_improvements(
+                    &mut commands,
+                    tile_connection_entity,
+                    improvements,
+                    &improvement_types,
+                    &mut unknown_improvement_types,
+                );
+                tile_connection_entity
+            };
 
-        let tile_connection_right_entity =
-            instance_tile_connection(tile_connection_right, &mut commands);
+        let tile_connection_right_entity = instance_tile_connection(tile_connection_right, right);
         let tile_connection_lower_right_entity =
-            instance_tile_connection(tile_connection_lower_right, &mut commands);
+            instance_tile_connection(tile_connection_lower_right, lower_right);
         let tile_connection_lower_left_entity =
-            instance_tile_connection(tile_connection_lower_left, &mut commands);
+            instance_tile_connection(tile_connection_lower_left, lower_left);
 
         tiles_and_connection_entities.push(Tile::new(
             tile_entity,
@@ -134,14 +742,107 @@ pub fn load_from_file(
         ));
     }
 
+    if !unknown_improvement_types.is_empty() {
+        for improvement_type in unknown_improvement_types {
+            handle_error(
+                &mut loading_errors,
+                &game_state_path,
+                format!("The improvement type `{improvement_type}` does not exist"),
+            );
+        }
+        return;
+    }
+
+    let hexagonal_map = HexagonalMap::from_vec(tiles_and_connection_entities).expect("BUG: Could not convert from `tiles_and_connection_entities` to a `HexagonalMap` despite the fact it was to convert from `tiles` to such a map.");
+    // Now that every tile exists, each connection can reference the tiles it lies between.
+    for (tile, coordinates) in hexagonal_map.iter() {
+        for (tile_connection_entity, neighbour) in tile
+            .tile_connection_entities()
+            .into_iter()
+            .zip(coordinates.neighbours())
+        {
+            if let Some(neighbour_tile) = hexagonal_map.get(neighbour) {
+                commands
+                    .entity(tile_connection_entity)
+                    .insert(ConnectedTiles(
+                        tile.tile_entity(),
+                        neighbour_tile.tile_entity(),
+                    ));
+            }
+        }
+    }
+
+    let tile_control = TileControl(HexagonalMap::from_vec(controllers).expect(
+        "BUG: There is exactly one controller per tile, so they form a `HexagonalMap` as well.",
+    ));
+    let supplied_factions: BTreeSet<_> = tile_supply.iter().flat_map(BTreeMap::keys).collect();
+    let supply = Supply(
+        supplied_factions
+            .into_iter()
+            .map(|faction| {
+                let faction_supply = tile_supply
+                    .iter()
+                    .map(|supply| supply.get(faction).copied().unwrap_or_default())
+                    .collect();
+                (
+                    faction.clone(),
+                    HexagonalMap::from_vec(faction_supply).expect(
+                        "BUG: There is exactly one supply per tile, so they form a `HexagonalMap` as well.",
+                    ),
+                )
+            })
+            .collect(),
+    );
+
+    let formation_entities = match spawn_formations(&mut commands, formations, &factions) {
+        Ok(formation_entities) => formation_entities,
+        Err(err) => {
+            handle_error(&mut loading_errors, &game_state_path, err);
+            return;
+        }
+    };
+    if let Err(err) = spawn_units(
+        &mut commands,
+        units,
+        &hexagonal_map,
+        &unit_types,
+        &factions,
+        &formation_entities,
+    ) {
+        handle_error(&mut loading_errors, &game_state_path, err);
+        return;
+    }
+
+    commands.insert_resource(ContentRoot(content_root));
+    commands.insert_resource(factions);
+    commands.insert_resource(active_faction);
+    commands.insert_resource(production);
+    commands.insert_resource(tile_control);
+    commands.insert_resource(supply);
+    let mut weather = Weather::new(
+        weather_zone_resources,
+        HexagonalMap::from_vec(tile_weather_zones)
+            .expect("BUG: There is exactly one weather zone per tile, so they form a `HexagonalMap` as well."),
+    );
+    weather.update(calendar.day_of_year(turn));
+
+    commands.insert_resource(Turn(turn));
+    commands.insert_resource(MapSeed(seed));
+    commands.insert_resource(game_rng);
+    commands.insert_resource(calendar);
+    commands.insert_resource(weather);
+    commands.insert_resource(weather_types);
+    commands.insert_resource(tile_types);
+    commands.insert_resource(unit_types);
+    commands.insert_resource(improvement_types);
     commands.insert_resource(LoadFromFileSuccessful { assets_to_load });
-    commands.insert_resource(HexagonalMap::from_vec(tiles_and_connection_entities).expect("BUG: Could not convert from `tiles_and_connection_entities` to a `HexagonalMap` despite the fact it was to convert from `tiles` to such a map."));
+    commands.insert_resource(hexagonal_map);
 }
 
 pub fn check_if_loaded(
     load_from_file_successful: Res<LoadFromFileSuccessful>,
     asset_server: Res<AssetServer>,
-    mut game_states: ResMut<NextState<GameStates>>,
+    mut loading_errors: ResMut<LoadingErrors>,
     mut gameplay_states: ResMut<NextState<GameplayStates>>,
 ) {
     let mut loaded = true;
@@ -149,10 +850,24 @@ pub fn check_if_loaded(
         match asset_server.load_state(handle.id()) {
             LoadState::Failed(err) => {
                 error!("Failed to load save file: {err}");
-                game_states.as_mut().set(GameStates::MainMenu);
-                return;
+                loading_errors.0.push(err.to_string());
+                loaded = false;
             }
-            LoadState::Loaded => continue,
+            // Assets like tile types depend on other assets like their textures.
+            LoadState::Loaded => match asset_server.recursive_dependency_load_state(handle.id()) {
+                RecursiveDependencyLoadState::Failed => {
+                    let path = asset_server
+                        .get_path(handle.id())
+                        .map(|path| path.to_string())
+                        .unwrap_or_default();
+                    let err = format!("A dependency of {path} could not be loaded");
+                    error!("Failed to load save file: {err}");
+                    loading_errors.0.push(err);
+                    loaded = false;
+                }
+                RecursiveDependencyLoadState::Loaded => continue,
+                _ => loaded = false,
+            },
             _ => loaded = false,
         };
     }
@@ -160,3 +875,30 @@ pub fn check_if_loaded(
         gameplay_states.as_mut().set(GameplayStates::InGame);
     }
 }
+
+pub fn show_loading_errors(
+    mut egui_context: Query<&mut EguiContext, With<PrimaryWindow>>,
+    loading_errors: Res<LoadingErrors>,
+    mut game_states: ResMut<NextState<GameStates>>,
+) {
+    if loading_errors.0.is_empty() {
+        return;
+    }
+    let Ok(mut egui_context) = egui_context.get_single_mut() else {
+        return;
+    };
+    Window::new("Could not load the game")
+        .collapsible(false)
+        .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(egui_context.get_mut(), |ui| {
+            ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                for loading_error in &loading_errors.0 {
+                    ui.label(RichText::new(loading_error).monospace());
+                    ui.separator();
+                }
+            });
+            if ui.button("Back to main menu").clicked() {
+                game_states.as_mut().set(GameStates::MainMenu);
+            }
+        });
+}
@@ -1,14 +1,27 @@
-use assets::{TileTypeLoader, UnitTypeLoader};
+use assets::{
+    ConnectionTypeLoader, ImprovementTypeLoader, TileTypeLoader, UnitTypeLoader, WeatherTypeLoader,
+};
 use bevy::{
     app::Plugin,
     asset::{AssetApp, Handle, ReflectAsset, ReflectHandle},
     prelude::{AppExtStates, OnEnter, ReflectResource, StateSet, SubStates},
     reflect::Reflect,
 };
-use components::{AxialCoordinates, ConnectedTiles, TileType};
+use components::{
+    AxialCoordinates, ConnectedTiles, ConnectionType, Elevation, Formation, Improvement, MoveOrder,
+    Owner, TerrainDamage, TerrainWork, TextureVariant, TileType, Unit, WeatherOverlay,
+};
+use events::{
+    Attack, BuildImprovement, ChangeTileType, MoveUnit, StartTerrainWork, TerrainEvent,
+    TileCaptured, TileEntered, TileFoughtOver, TurnEnded,
+};
 use in_game_plugin::InGamePlugin;
 use loading_screen_plugin::LoadingScreenPlugin;
-use resources::{HexagonalMap, Tile};
+use resources::{
+    ActiveFaction, Calendar, ContentRoot, Factions, GameRng, HexagonalMap, ImprovementTypes,
+    MapSeed, Production, Supply, Tile, TileControl, TileMovementCosts, TileTypes, Turn, UnitTypes,
+    Weather, WeatherTypes,
+};
 use systems::setup;
 
 use crate::{cleanup::Cleanup, GameStates};
@@ -17,10 +30,23 @@ mod in_game_plugin;
 mod loading_screen_plugin;
 
 mod assets;
+mod combat;
 mod components;
+mod content_library;
+mod elevation;
+mod events;
+mod improvements;
+mod influence_map;
+mod movement;
+mod pathfinding;
+mod random;
 mod resources;
 mod save_file;
+mod supply;
 mod systems;
+mod units;
+mod vision;
+mod weather;
 
 pub struct GameplayPlugin;
 
@@ -31,13 +57,84 @@ impl Plugin for GameplayPlugin {
 
         // Components
         app.register_type::<ConnectedTiles>()
+            .register_type::<ConnectionType>()
             .register_type::<TileType>()
+            .register_type::<Elevation>()
+            .register_type::<Owner>()
+            .register_type::<Unit>()
+            .register_type::<Formation>()
+            .register_type::<MoveOrder>()
+            .register_type::<Improvement>()
+            .register_type::<TextureVariant>()
+            .register_type::<TerrainDamage>()
+            .register_type::<TerrainWork>()
+            .register_type::<WeatherOverlay>()
             .register_type::<AxialCoordinates>();
 
         // Resources
         app.register_type::<HexagonalMap<Tile>>()
             .register_type_data::<HexagonalMap<Tile>, ReflectResource>()
             .cleanup_resource::<HexagonalMap<Tile>>(GameStates::Gameplay);
+        app.register_type::<TileTypes>()
+            .register_type_data::<TileTypes, ReflectResource>()
+            .cleanup_resource::<TileTypes>(GameStates::Gameplay);
+        app.register_type::<UnitTypes>()
+            .register_type_data::<UnitTypes, ReflectResource>()
+            .cleanup_resource::<UnitTypes>(GameStates::Gameplay);
+        app.register_type::<TileMovementCosts>()
+            .register_type_data::<TileMovementCosts, ReflectResource>()
+            .cleanup_resource::<TileMovementCosts>(GameStates::Gameplay);
+        app.register_type::<ContentRoot>()
+            .register_type_data::<ContentRoot, ReflectResource>()
+            .cleanup_resource::<ContentRoot>(GameStates::Gameplay);
+        app.register_type::<Factions>()
+            .register_type_data::<Factions, ReflectResource>()
+            .cleanup_resource::<Factions>(GameStates::Gameplay);
+        app.register_type::<ActiveFaction>()
+            .register_type_data::<ActiveFaction, ReflectResource>()
+            .cleanup_resource::<ActiveFaction>(GameStates::Gameplay);
+        app.register_type::<ImprovementTypes>()
+            .register_type_data::<ImprovementTypes, ReflectResource>()
+            .cleanup_resource::<ImprovementTypes>(GameStates::Gameplay);
+        app.register_type::<Turn>()
+            .register_type_data::<Turn, ReflectResource>()
+            .cleanup_resource::<Turn>(GameStates::Gameplay);
+        app.register_type::<MapSeed>()
+            .register_type_data::<MapSeed, ReflectResource>()
+            .cleanup_resource::<MapSeed>(GameStates::Gameplay);
+        app.register_type::<GameRng>()
+            .register_type_data::<GameRng, ReflectResource>()
+            .cleanup_resource::<GameRng>(GameStates::Gameplay);
+        app.register_type::<TileControl>()
+            .register_type_data::<TileControl, ReflectResource>()
+            .cleanup_resource::<TileControl>(GameStates::Gameplay);
+        app.register_type::<Production>()
+            .register_type_data::<Production, ReflectResource>()
+            .cleanup_resource::<Production>(GameStates::Gameplay);
+        app.register_type::<Supply>()
+            .register_type_data::<Supply, ReflectResource>()
+            .cleanup_resource::<Supply>(GameStates::Gameplay);
+        app.register_type::<Calendar>()
+            .register_type_data::<Calendar, ReflectResource>()
+            .cleanup_resource::<Calendar>(GameStates::Gameplay);
+        app.register_type::<Weather>()
+            .register_type_data::<Weather, ReflectResource>()
+            .cleanup_resource::<Weather>(GameStates::Gameplay);
+        app.register_type::<WeatherTypes>()
+            .register_type_data::<WeatherTypes, ReflectResource>()
+            .cleanup_resource::<WeatherTypes>(GameStates::Gameplay);
+
+        // Events
+        app.add_event::<TileEntered>()
+            .add_event::<TileCaptured>()
+            .add_event::<TurnEnded>()
+            .add_event::<MoveUnit>()
+            .add_event::<Attack>()
+            .add_event::<BuildImprovement>()
+            .add_event::<TileFoughtOver>()
+            .add_event::<StartTerrainWork>()
+            .add_event::<TerrainEvent>()
+            .add_event::<ChangeTileType>();
 
         // Assets, asset loaders and their handles.
         app.init_asset::<assets::TileType>()
@@ -52,6 +149,24 @@ impl Plugin for GameplayPlugin {
             .register_type_data::<assets::UnitType, ReflectAsset>()
             .register_type::<Handle<assets::UnitType>>()
             .register_type_data::<Handle<assets::UnitType>, ReflectHandle>();
+        app.init_asset::<assets::ConnectionType>()
+            .init_asset_loader::<ConnectionTypeLoader>()
+            .register_type::<assets::ConnectionType>()
+            .register_type_data::<assets::ConnectionType, ReflectAsset>()
+            .register_type::<Handle<assets::ConnectionType>>()
+            .register_type_data::<Handle<assets::ConnectionType>, ReflectHandle>();
+        app.init_asset::<assets::ImprovementType>()
+            .init_asset_loader::<ImprovementTypeLoader>()
+            .register_type::<assets::ImprovementType>()
+            .register_type_data::<assets::ImprovementType, ReflectAsset>()
+            .register_type::<Handle<assets::ImprovementType>>()
+            .register_type_data::<Handle<assets::ImprovementType>, ReflectHandle>();
+        app.init_asset::<assets::WeatherType>()
+            .init_asset_loader::<WeatherTypeLoader>()
+            .register_type::<assets::WeatherType>()
+            .register_type_data::<assets::WeatherType, ReflectAsset>()
+            .register_type::<Handle<assets::WeatherType>>()
+            .register_type_data::<Handle<assets::WeatherType>, ReflectHandle>();
 
         // Other systems.
         app.add_systems(OnEnter(GameStates::Gameplay), setup);
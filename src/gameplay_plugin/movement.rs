@@ -0,0 +1,203 @@
+//! How units move across tiles and the connections between them.
+
+use super::{
+    assets::{ConnectionType, MovementClass, MovementCosts},
+    components::{AxialCoordinates, Elevation},
+    elevation,
+    pathfinding::MovementCost,
+    save_file::FactionId,
+};
+
+/// The movement cost for a unit of `movement_class` to step from a tile at the elevation `from` onto a tile with `movement_costs` at the elevation `to`.
+/// `connection_type` is the connection between both tiles, `None` for plain borders and connections negated by an improvement like a bridge.
+/// Every step costs at least one movement point.
+///
+/// ## Returns
+/// - None: The tile is impassable for the movement class or the connection can not be crossed.
+pub fn step_cost(
+    movement_class: MovementClass,
+    movement_costs: &MovementCosts,
+    connection_type: Option<&ConnectionType>,
+    from: Elevation,
+    to: Elevation,
+) -> Option<MovementCost> {
+    let tile_movement_cost = movement_costs.get(movement_class)?;
+    let movement_cost = match connection_type {
+        Some(connection_type) => connection_type.movement_cost_across(tile_movement_cost)?,
+        None => tile_movement_cost,
+    };
+    Some(MovementCost::from(movement_cost).max(1) + elevation::climb_cost(from, to))
+}
+
+/// Added to the movement cost of leaving a tile in an enemy zone of control.
+pub const ZONE_OF_CONTROL_EXIT_COST: MovementCost = 2;
+
+/// Whether the tile at `coordinates` lies in the zone of control of one of the `units` not belonging to `faction`.
+/// Units exert a zone of control onto their adjacent tiles, except across connections for which `negates_zone_of_control(unit_coordinates, coordinates)` is true, e.g. rivers.
+/// Units entering an enemy zone of control have to stop there for the turn, and leaving it costs [ZONE_OF_CONTROL_EXIT_COST] extra.
+pub fn in_enemy_zone_of_control<'a>(
+    coordinates: AxialCoordinates,
+    faction: &FactionId,
+    units: impl IntoIterator<Item = (AxialCoordinates, &'a FactionId)>,
+    negates_zone_of_control: impl Fn(AxialCoordinates, AxialCoordinates) -> bool,
+) -> bool {
+    let neighbours = coordinates.neighbours();
+    units.into_iter().any(|(unit_coordinates, owner)| {
+        owner != faction
+            && neighbours.contains(&unit_coordinates)
+            && !negates_zone_of_control(unit_coordinates, coordinates)
+    })
+}
+
+/// How many units of a faction fit onto a tile.
+pub const STACKING_LIMIT: usize = 4;
+
+/// How many more units of `faction` fit onto the tile at `coordinates` besides the `units` standing on the map.
+pub fn room_on_tile<'a>(
+    coordinates: AxialCoordinates,
+    faction: &FactionId,
+    units: impl IntoIterator<Item = (AxialCoordinates, &'a FactionId)>,
+) -> usize {
+    let stack = units
+        .into_iter()
+        .filter(|(unit_coordinates, owner)| *unit_coordinates == coordinates && *owner == faction)
+        .count();
+    STACKING_LIMIT.saturating_sub(stack)
+}
+
+/// The destinations of `amount` units which are ordered to `destination` as one.
+/// They fill up the destination and then its neighbours, with `room` returning how many more units fit onto a tile.
+/// Units that fit nowhere are sent to the destination anyway, where they wait until there is room.
+pub fn spread_over_stacks(
+    destination: AxialCoordinates,
+    amount: usize,
+    mut room: impl FnMut(AxialCoordinates) -> usize,
+) -> Vec<AxialCoordinates> {
+    let mut destinations = Vec::with_capacity(amount);
+    for coordinates in [destination].into_iter().chain(destination.neighbours()) {
+        let fitting = room(coordinates).min(amount - destinations.len());
+        destinations.extend(std::iter::repeat(coordinates).take(fitting));
+    }
+    destinations.resize(amount, destination);
+    destinations
+}
+
+#[cfg(test)]
+mod tests_movement {
+    use ron::de::from_str;
+
+    use super::{
+        in_enemy_zone_of_control, room_on_tile, spread_over_stacks, step_cost, STACKING_LIMIT,
+    };
+    use crate::gameplay_plugin::{
+        assets::{MovementClass, MovementCosts},
+        components::{AxialCoordinates, Elevation},
+    };
+
+    #[test]
+    fn test_step_cost() {
+        let movement_costs =
+            from_str::<MovementCosts>("(foot: Some(2), wheeled: None, tracked: Some(0))").unwrap();
+        let flat = Elevation::new(0);
+        let hill = Elevation::new(2);
+        assert_eq!(
+            step_cost(MovementClass::Foot, &movement_costs, None, flat, flat),
+            Some(2)
+        );
+        assert_eq!(
+            step_cost(MovementClass::Wheeled, &movement_costs, None, flat, flat),
+            None
+        );
+        // Entering a tile is never free.
+        assert_eq!(
+            step_cost(MovementClass::Tracked, &movement_costs, None, flat, flat),
+            Some(1)
+        );
+        // Climbing costs extra, descending doesn't.
+        assert_eq!(
+            step_cost(MovementClass::Foot, &movement_costs, None, flat, hill),
+            Some(4)
+        );
+        assert_eq!(
+            step_cost(MovementClass::Foot, &movement_costs, None, hill, flat),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_in_enemy_zone_of_control() {
+        let red = "red".to_string();
+        let blue = "blue".to_string();
+        let units = [
+            (AxialCoordinates::new(0, 0), &red),
+            (AxialCoordinates::new(3, 0), &blue),
+        ];
+        assert!(in_enemy_zone_of_control(
+            AxialCoordinates::new(1, 0),
+            &blue,
+            units,
+            |_, _| false
+        ));
+        // Own units have no zone of control for their faction.
+        assert!(!in_enemy_zone_of_control(
+            AxialCoordinates::new(1, 0),
+            &red,
+            units,
+            |_, _| false
+        ));
+        assert!(!in_enemy_zone_of_control(
+            AxialCoordinates::new(2, -2),
+            &blue,
+            units,
+            |_, _| false
+        ));
+
+        // A river between the units negates the zone of control.
+        let river = (AxialCoordinates::new(0, 0), AxialCoordinates::new(1, 0));
+        assert!(!in_enemy_zone_of_control(
+            AxialCoordinates::new(1, 0),
+            &blue,
+            units,
+            |from, to| (from, to) == river
+        ));
+        assert!(in_enemy_zone_of_control(
+            AxialCoordinates::new(0, 1),
+            &blue,
+            units,
+            |from, to| (from, to) == river
+        ));
+    }
+
+    #[test]
+    fn test_stacking() {
+        let red = "red".to_string();
+        let blue = "blue".to_string();
+        let tile = AxialCoordinates::new(0, 0);
+        let units = [(tile, &red), (tile, &red), (tile, &blue)];
+        assert_eq!(room_on_tile(tile, &red, units), STACKING_LIMIT - 2);
+        assert_eq!(room_on_tile(tile, &blue, units), STACKING_LIMIT - 1);
+        assert_eq!(
+            room_on_tile(AxialCoordinates::new(1, 0), &red, units),
+            STACKING_LIMIT
+        );
+
+        // The destination is filled up first, then its neighbours clockwise starting right.
+        let destinations = spread_over_stacks(
+            tile,
+            4,
+            |coordinates| {
+                if coordinates == tile {
+                    3
+                } else {
+                    1
+                }
+            },
+        );
+        assert_eq!(
+            destinations,
+            vec![tile, tile, tile, AxialCoordinates::new(1, 0)]
+        );
+        // Units that fit nowhere wait at the destination.
+        assert_eq!(spread_over_stacks(tile, 2, |_| 0), vec![tile, tile]);
+    }
+}
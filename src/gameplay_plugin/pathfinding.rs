@@ -0,0 +1,121 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use super::{
+    components::AxialCoordinates,
+    resources::{radius_and_amount_tiles::Radius, HexagonalMap},
+};
+
+pub type MovementCost = u32;
+
+/// Calculate the cheapest movement cost from any of the `origins` to every tile of a map with the given radius (Dijkstra).
+///
+/// `cost` returns the cost of stepping from the first to the second (adjacent) coordinates or `None` if that step is impossible.
+/// Tiles that are unreachable or more expensive to reach than `max_cost` are `None`.
+pub fn movement_costs_from(
+    radius: Radius,
+    origins: impl IntoIterator<Item = AxialCoordinates>,
+    max_cost: MovementCost,
+    mut cost: impl FnMut(AxialCoordinates, AxialCoordinates) -> Option<MovementCost>,
+) -> HexagonalMap<Option<MovementCost>> {
+    let mut costs = HexagonalMap::from_fn(radius, |_| None);
+    let mut frontier: BinaryHeap<(Reverse<MovementCost>, i32, i32)> = BinaryHeap::new();
+    for origin in origins {
+        if let Some(origin_cost) = costs.get_mut(origin) {
+            *origin_cost = Some(0);
+            frontier.push((Reverse(0), origin.q(), origin.r()));
+        }
+    }
+
+    while let Some((Reverse(current_cost), q, r)) = frontier.pop() {
+        let current = AxialCoordinates::new(q, r);
+        // A cheaper way to this tile has already been processed.
+        if costs.get(current).copied().flatten() < Some(current_cost) {
+            continue;
+        }
+        for neighbour in current.neighbours() {
+            let Some(step_cost) = cost(current, neighbour) else {
+                continue;
+            };
+            let neighbour_cost = current_cost.saturating_add(step_cost);
+            if neighbour_cost > max_cost {
+                continue;
+            }
+            let Some(best_cost) = costs.get_mut(neighbour) else {
+                continue;
+            };
+            if best_cost.map_or(true, |best_cost| neighbour_cost < best_cost) {
+                *best_cost = Some(neighbour_cost);
+                frontier.push((Reverse(neighbour_cost), neighbour.q(), neighbour.r()));
+            }
+        }
+    }
+
+    costs
+}
+
+/// The cheapest path to `destination` through `costs` calculated by [movement_costs_from()] with the same `cost`.
+/// The path starts with the tile after the origin and ends with the destination, so it is empty if the destination is an origin.
+///
+/// ## Returns
+/// - None: The destination is unreachable.
+pub fn path_to(
+    costs: &HexagonalMap<Option<MovementCost>>,
+    destination: AxialCoordinates,
+    mut cost: impl FnMut(AxialCoordinates, AxialCoordinates) -> Option<MovementCost>,
+) -> Option<Vec<AxialCoordinates>> {
+    let mut current = destination;
+    let mut current_cost = (*costs.get(destination)?)?;
+    let mut path = vec![];
+    // Walk back from the destination to a tile the cheapest way could have come from, until reaching an origin.
+    while current_cost > 0 {
+        path.push(current);
+        let (previous, previous_cost) = current
+            .neighbours()
+            .into_iter()
+            .filter_map(|neighbour| Some((neighbour, (*costs.get(neighbour)?)?)))
+            .find(|(neighbour, neighbour_cost)| {
+                cost(*neighbour, current)
+                    .is_some_and(|step_cost| neighbour_cost + step_cost == current_cost)
+            })?;
+        current = previous;
+        current_cost = previous_cost;
+    }
+    path.reverse();
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests_pathfinding {
+    use super::{movement_costs_from, path_to, MovementCost};
+    use crate::gameplay_plugin::{
+        components::AxialCoordinates, resources::radius_and_amount_tiles::Radius,
+    };
+
+    #[test]
+    fn test_path_to() {
+        let origin = AxialCoordinates::new(-2, 0);
+        let wall = [AxialCoordinates::new(-1, 0), AxialCoordinates::new(-1, -1)];
+        let cost = |_: AxialCoordinates, to: AxialCoordinates| -> Option<MovementCost> {
+            (!wall.contains(&to)).then_some(1)
+        };
+        let costs = movement_costs_from(Radius::Radius2, [origin], MovementCost::MAX, cost);
+        let destination = AxialCoordinates::new(0, 0);
+        let path = path_to(&costs, destination, cost).unwrap();
+        assert_eq!(path.last(), Some(&destination));
+        assert_eq!(
+            path.len() as MovementCost,
+            costs.get(destination).unwrap().unwrap()
+        );
+        // Every step goes to an adjacent tile around the wall.
+        let mut previous = origin;
+        for step in path {
+            assert!(previous.neighbours().contains(&step));
+            assert!(!wall.contains(&step));
+            previous = step;
+        }
+
+        assert_eq!(path_to(&costs, origin, cost), Some(vec![]));
+        let unreachable = movement_costs_from(Radius::Radius2, [origin], 1, cost);
+        assert_eq!(path_to(&unreachable, destination, cost), None);
+    }
+}
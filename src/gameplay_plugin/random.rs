@@ -0,0 +1,125 @@
+//! Reproducible randomness, so a game started from the same seed plays out the same for the same orders.
+//! This is what replays, lockstep multiplayer and tests of random outcomes rely on.
+
+use std::hash::{BuildHasher, RandomState};
+
+use bevy::reflect::Reflect;
+
+/// The increment of SplitMix64.
+pub const GOLDEN_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// The finalizer of SplitMix64, which turns similar values into very different ones.
+pub fn mix(value: u64) -> u64 {
+    let mut hash = value;
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^ (hash >> 31)
+}
+
+/// A seed for games whose scenario doesn't define one. It differs between every call.
+pub fn random_seed() -> u64 {
+    RandomState::new().hash_one(GOLDEN_GAMMA)
+}
+
+/// The subsystems drawing random numbers.
+/// Each one draws from its own stream, so e.g. how often the AI draws never changes the outcome of combats.
+#[derive(Reflect, Copy, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RngStream {
+    Combat,
+    /// Reserved for the AI, which doesn't exist yet. It has its own stream already, so save files don't change once it draws.
+    Ai,
+    /// Reserved for random weather. Weather currently follows the fixed schedule of the seasons of each zone.
+    Weather,
+}
+
+impl RngStream {
+    pub const ALL: [RngStream; 3] = [RngStream::Combat, RngStream::Ai, RngStream::Weather];
+    pub const COUNT: usize = RngStream::ALL.len();
+
+    pub fn index(self) -> usize {
+        match self {
+            RngStream::Combat => 0,
+            RngStream::Ai => 1,
+            RngStream::Weather => 2,
+        }
+    }
+}
+
+/// The number drawn as the `draw`th one from `stream` of a game with `seed`.
+/// Every stream is a SplitMix64 sequence starting at a state derived from the seed and the stream.
+pub fn draw(seed: u64, stream: RngStream, draw: u64) -> u64 {
+    let state = mix(seed ^ mix(stream.index() as u64 + 1));
+    mix(state.wrapping_add(draw.wrapping_add(1).wrapping_mul(GOLDEN_GAMMA)))
+}
+
+/// Turn a random number into a uniformly distributed one in `[0, 1)`.
+pub fn unit_interval(value: u64) -> f32 {
+    // An f32 has 24 bits of precision.
+    (value >> 40) as f32 / (1u64 << 24) as f32
+}
+
+#[cfg(test)]
+mod tests_random {
+    use super::{draw, unit_interval, RngStream};
+    use crate::gameplay_plugin::resources::GameRng;
+
+    #[test]
+    fn test_same_seed_draws_same_numbers() {
+        let mut first = GameRng::new(42, [0; RngStream::COUNT]);
+        let mut second = GameRng::new(42, [0; RngStream::COUNT]);
+        for _ in 0..100 {
+            assert_eq!(
+                first.next_u64(RngStream::Combat),
+                second.next_u64(RngStream::Combat)
+            );
+        }
+        let mut other_seed = GameRng::new(43, [0; RngStream::COUNT]);
+        assert_ne!(
+            first.next_u64(RngStream::Combat),
+            other_seed.next_u64(RngStream::Combat)
+        );
+    }
+
+    #[test]
+    fn test_streams_are_independent() {
+        let mut rng = GameRng::new(7, [0; RngStream::COUNT]);
+        let mut reference = GameRng::new(7, [0; RngStream::COUNT]);
+        for _ in 0..10 {
+            rng.next_u64(RngStream::Ai);
+        }
+        assert_eq!(
+            rng.next_u64(RngStream::Combat),
+            reference.next_u64(RngStream::Combat)
+        );
+        assert_eq!(rng.draws(RngStream::Ai), 10);
+        assert_eq!(rng.draws(RngStream::Combat), 1);
+        assert_ne!(
+            draw(7, RngStream::Combat, 0),
+            draw(7, RngStream::Weather, 0)
+        );
+    }
+
+    #[test]
+    fn test_continues_after_loading() {
+        let mut rng = GameRng::new(5, [0; RngStream::COUNT]);
+        rng.next_u64(RngStream::Weather);
+        let draws = RngStream::ALL.map(|stream| rng.draws(stream));
+        let mut loaded = GameRng::new(rng.seed(), draws);
+        assert_eq!(
+            rng.next_u64(RngStream::Weather),
+            loaded.next_u64(RngStream::Weather)
+        );
+    }
+
+    #[test]
+    fn test_unit_interval() {
+        assert_eq!(unit_interval(0), 0.0);
+        assert!(unit_interval(u64::MAX) < 1.0);
+        let mut rng = GameRng::new(1, [0; RngStream::COUNT]);
+        let mean = (0..1000)
+            .map(|_| rng.next_f32(RngStream::Combat))
+            .sum::<f32>()
+            / 1000.0;
+        assert!((mean - 0.5).abs() < 0.05);
+    }
+}
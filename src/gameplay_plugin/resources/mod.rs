@@ -1,6 +1,11 @@
-use std::cmp::{max, min, Ordering};
+use std::{
+    cmp::{max, min, Ordering},
+    collections::HashMap,
+};
 
 use bevy::{
+    asset::{Assets, Handle},
+    color::Color,
     prelude::{Entity, Resource},
     reflect::Reflect,
 };
@@ -8,7 +13,16 @@ use radius_and_amount_tiles::{AmountTiles, Radius};
 
 use self::radius_and_amount_tiles::InvalidTileAmount;
 
-use super::components::AxialCoordinates;
+use super::{
+    assets::{ImprovementType, MovementCosts, TileType, UnitType, WeatherType},
+    components::AxialCoordinates,
+    events::TileCaptured,
+    random::{self, RngStream},
+    save_file::{
+        FactionId, ImprovementTypePath, TileTypePath, UnitTypePath, WeatherTypePath, WeatherZoneId,
+    },
+    weather::{scheduled_weather, Season, DAYS_PER_YEAR},
+};
 
 pub mod radius_and_amount_tiles;
 
@@ -128,6 +142,10 @@ impl Tile {
         }
     }
 
+    pub fn tile_entity(&self) -> Entity {
+        self.tile_entity
+    }
+
     pub fn tile_connection_right_entity(&self) -> Entity {
         self.tile_connection_right_entity
     }
@@ -139,8 +157,391 @@ impl Tile {
     pub fn tile_connection_lower_left_entity(&self) -> Entity {
         self.tile_connection_lower_left_entity
     }
+
+    /// The connections owned by this tile, in the same order as the first three of [AxialCoordinates::neighbours()].
+    pub fn tile_connection_entities(&self) -> [TileConnectionEntity; 3] {
+        [
+            self.tile_connection_right_entity,
+            self.tile_connection_lower_right_entity,
+            self.tile_connection_lower_left_entity,
+        ]
+    }
+}
+
+/// The unit types available in the current scenario by their path (their directory name), including the ones from the library.
+#[derive(Resource, Reflect, Default, Debug, Clone, PartialEq, Eq)]
+pub struct UnitTypes(pub HashMap<UnitTypePath, Handle<UnitType>>);
+
+impl UnitTypes {
+    pub fn get(&self, unit_type: &str) -> Option<&Handle<UnitType>> {
+        self.0.get(unit_type)
+    }
+}
+
+/// The directory (relative to `assets`) content like tile types is resolved from.
+/// This is the scenario the game was started from, even if it was saved and loaded from elsewhere since.
+#[derive(Resource, Reflect, Debug, Clone, PartialEq, Eq)]
+pub struct ContentRoot(pub String);
+
+#[derive(Reflect, Debug, Clone, PartialEq)]
+pub struct Faction {
+    id: FactionId,
+    display_name: String,
+    color: Color,
+}
+
+impl Faction {
+    pub fn new(id: FactionId, display_name: String, color: Color) -> Self {
+        Self {
+            id,
+            display_name,
+            color,
+        }
+    }
+
+    pub fn id(&self) -> &FactionId {
+        &self.id
+    }
+
+    pub fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    pub fn color(&self) -> Color {
+        self.color
+    }
+}
+
+/// Every faction taking part in the game, in the order they were defined in the save file.
+#[derive(Resource, Reflect, Default, Debug, Clone, PartialEq)]
+pub struct Factions(pub Vec<Faction>);
+
+impl Factions {
+    pub fn get(&self, id: &str) -> Option<&Faction> {
+        self.0.iter().find(|faction| faction.id() == id)
+    }
+
+    /// The faction defined after `id`.
+    ///
+    /// ## Returns
+    /// - None: If `id` is the last or an unknown faction.
+    pub fn after(&self, id: &str) -> Option<&Faction> {
+        let index = self.0.iter().position(|faction| faction.id() == id)?;
+        self.0.get(index + 1)
+    }
+}
+
+/// The faction whose turn it is, which is the only one the player can give orders to.
+/// Factions take their turns one after another in the order of [Factions]. `None` if there are no factions.
+#[derive(Resource, Reflect, Default, Debug, Clone, PartialEq, Eq)]
+pub struct ActiveFaction(pub Option<FactionId>);
+
+impl ActiveFaction {
+    pub fn is(&self, faction: &str) -> bool {
+        self.0.as_deref() == Some(faction)
+    }
+}
+
+/// The faction controlling each tile, if any.
+/// Victory points, supply and production depend on who controls which tiles.
+#[derive(Resource, Reflect, Debug, Clone, PartialEq, Eq)]
+pub struct TileControl(pub HexagonalMap<Option<FactionId>>);
+
+impl TileControl {
+    pub fn controller(&self, coordinates: AxialCoordinates) -> Option<&FactionId> {
+        self.0.get(coordinates).and_then(Option::as_ref)
+    }
+
+    /// Let `faction` capture the tile at `coordinates` after one of its units entered it.
+    /// The tile is only captured if it is undefended, i.e. none of the factions in `present_factions` is hostile to `faction`.
+    ///
+    /// ## Returns
+    /// - Some: If the tile changed its controller.
+    /// - None: If the tile is defended, already controlled by `faction` or not on the map.
+    pub fn capture<'a>(
+        &mut self,
+        coordinates: AxialCoordinates,
+        faction: &FactionId,
+        mut present_factions: impl Iterator<Item = &'a FactionId>,
+    ) -> Option<TileCaptured> {
+        if present_factions.any(|present_faction| present_faction != faction) {
+            return None;
+        }
+        let controller = self.0.get_mut(coordinates)?;
+        if controller.as_ref() == Some(faction) {
+            return None;
+        }
+        let previous_controller = controller.replace(faction.clone());
+        Some(TileCaptured {
+            coordinates,
+            previous_controller,
+            controller: faction.clone(),
+        })
+    }
 }
 
+/// How much supply reaches each tile for each faction, see [crate::gameplay_plugin::supply].
+/// It is traced at the start of every turn, so units moving during a turn are supplied from where they started it.
+#[derive(Resource, Reflect, Default, Debug, Clone, PartialEq, Eq)]
+pub struct Supply(pub HashMap<FactionId, HexagonalMap<u8>>);
+
+impl Supply {
+    pub fn get(&self, faction: &str, coordinates: AxialCoordinates) -> u8 {
+        self.0
+            .get(faction)
+            .and_then(|supply| supply.get(coordinates))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn in_supply(&self, faction: &str, coordinates: AxialCoordinates) -> bool {
+        self.get(faction, coordinates) > 0
+    }
+
+    /// Cut the supply of every faction other than `controller` on a tile it just captured.
+    pub fn cut(&mut self, coordinates: AxialCoordinates, controller: &str) {
+        for (faction, supply) in &mut self.0 {
+            if faction == controller {
+                continue;
+            }
+            if let Some(supply) = supply.get_mut(coordinates) {
+                *supply = 0;
+            }
+        }
+    }
+}
+
+/// The production every faction has stockpiled to build improvements with. It grows with every tile the faction controls.
+#[derive(Resource, Reflect, Default, Debug, Clone, PartialEq, Eq)]
+pub struct Production(pub HashMap<FactionId, u32>);
+
+impl Production {
+    pub fn get(&self, faction: &str) -> u32 {
+        self.0.get(faction).copied().unwrap_or_default()
+    }
+
+    pub fn gain(&mut self, faction: &str, amount: u32) {
+        let production = self.0.entry(faction.to_string()).or_default();
+        *production = production.saturating_add(amount);
+    }
+
+    /// Spend `cost` from the production of `faction`, if it has enough.
+    ///
+    /// ## Returns
+    /// Whether the production was spent.
+    pub fn spend(&mut self, faction: &str, cost: u32) -> bool {
+        match self.0.get_mut(faction) {
+            Some(production) if *production >= cost => {
+                *production -= cost;
+                true
+            }
+            _ => cost == 0,
+        }
+    }
+}
+
+/// The improvement types available in the current scenario by their path (their directory name), including the ones from the library.
+#[derive(Resource, Reflect, Default, Debug, Clone, PartialEq, Eq)]
+pub struct ImprovementTypes(pub HashMap<ImprovementTypePath, Handle<ImprovementType>>);
+
+impl ImprovementTypes {
+    pub fn get(&self, improvement_type: &str) -> Option<&Handle<ImprovementType>> {
+        self.0.get(improvement_type)
+    }
+}
+
+/// The tile types available in the current scenario by their path (their directory name), including the ones from the library.
+/// All of them are loaded, so tiles can change their tile type without waiting for it to load.
+#[derive(Resource, Reflect, Default, Debug, Clone, PartialEq, Eq)]
+pub struct TileTypes(pub HashMap<TileTypePath, Handle<TileType>>);
+
+impl TileTypes {
+    pub fn get(&self, tile_type: &str) -> Option<&Handle<TileType>> {
+        self.0.get(tile_type)
+    }
+}
+
+/// Decides the texture variant of every tile, see [crate::gameplay_plugin::assets::texture_variants::TextureVariants::pick()].
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MapSeed(pub u64);
+
+/// The source of all randomness in a game, see [crate::gameplay_plugin::random].
+#[derive(Resource, Reflect, Debug, Clone, PartialEq, Eq)]
+pub struct GameRng {
+    seed: u64,
+    /// How many numbers were drawn from each stream so far, by [RngStream::index()].
+    draws: [u64; RngStream::COUNT],
+}
+
+impl GameRng {
+    pub fn new(seed: u64, draws: [u64; RngStream::COUNT]) -> Self {
+        Self { seed, draws }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// How many numbers were drawn from `stream` so far.
+    /// Saved with the seed, so a loaded game continues with the same numbers.
+    pub fn draws(&self, stream: RngStream) -> u64 {
+        self.draws[stream.index()]
+    }
+
+    pub fn next_u64(&mut self, stream: RngStream) -> u64 {
+        let draws = &mut self.draws[stream.index()];
+        let value = random::draw(self.seed, stream, *draws);
+        *draws += 1;
+        value
+    }
+
+    /// A number in `[0, 1)`.
+    pub fn next_f32(&mut self, stream: RngStream) -> f32 {
+        random::unit_interval(self.next_u64(stream))
+    }
+}
+
+/// The current turn, starting at 1.
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Turn(pub u32);
+
+/// Maps turns to days of the year.
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Calendar {
+    start_day: u16,
+    days_per_turn: u16,
+}
+
+impl Calendar {
+    pub fn new(start_day: u16, days_per_turn: u16) -> Self {
+        Self {
+            start_day,
+            days_per_turn,
+        }
+    }
+
+    /// The day of the year of the first turn, starting at 0 for the first of January.
+    pub fn start_day(&self) -> u16 {
+        self.start_day
+    }
+
+    pub fn days_per_turn(&self) -> u16 {
+        self.days_per_turn
+    }
+
+    /// The day of the year during `turn`.
+    pub fn day_of_year(&self, turn: u32) -> u16 {
+        let days = u64::from(self.start_day)
+            + u64::from(turn.saturating_sub(1)) * u64::from(self.days_per_turn);
+        (days % u64::from(DAYS_PER_YEAR)) as u16
+    }
+}
+
+/// The weather types available to the scenario by their id.
+#[derive(Resource, Reflect, Default, Debug, Clone, PartialEq, Eq)]
+pub struct WeatherTypes(pub HashMap<WeatherTypePath, Handle<WeatherType>>);
+
+impl WeatherTypes {
+    pub fn get(&self, weather_type: &str) -> Option<&Handle<WeatherType>> {
+        self.0.get(weather_type)
+    }
+}
+
+#[derive(Reflect, Debug, Clone, PartialEq, Eq)]
+pub struct WeatherZone {
+    id: WeatherZoneId,
+    seasons: Vec<Season>,
+    /// The current weather, `None` if it is clear.
+    weather: Option<WeatherTypePath>,
+}
+
+impl WeatherZone {
+    pub fn new(id: WeatherZoneId, seasons: Vec<Season>) -> Self {
+        Self {
+            id,
+            seasons,
+            weather: None,
+        }
+    }
+
+    pub fn id(&self) -> &WeatherZoneId {
+        &self.id
+    }
+
+    pub fn seasons(&self) -> &[Season] {
+        &self.seasons
+    }
+
+    pub fn weather(&self) -> Option<&WeatherTypePath> {
+        self.weather.as_ref()
+    }
+}
+
+/// The weather zones and the zone of every tile.
+#[derive(Resource, Reflect, Debug, Clone, PartialEq, Eq)]
+pub struct Weather {
+    zones: Vec<WeatherZone>,
+    /// The index of the zone of every tile in `zones`.
+    tile_zones: HexagonalMap<Option<usize>>,
+}
+
+impl Weather {
+    pub fn new(zones: Vec<WeatherZone>, tile_zones: HexagonalMap<Option<usize>>) -> Self {
+        Self { zones, tile_zones }
+    }
+
+    pub fn zones(&self) -> &[WeatherZone] {
+        &self.zones
+    }
+
+    /// The zone of the tile at `coordinates`, if it lies in one.
+    pub fn zone(&self, coordinates: AxialCoordinates) -> Option<&WeatherZone> {
+        let zone = (*self.tile_zones.get(coordinates)?)?;
+        self.zones.get(zone)
+    }
+
+    /// The current weather at `coordinates`, `None` if it is clear.
+    pub fn weather_at(&self, coordinates: AxialCoordinates) -> Option<&WeatherTypePath> {
+        self.zone(coordinates).and_then(WeatherZone::weather)
+    }
+
+    /// The current weather type at `coordinates`, if it is loaded.
+    pub fn weather_type_at<'a>(
+        &self,
+        coordinates: AxialCoordinates,
+        weather_types: &WeatherTypes,
+        weather_type_assets: &'a Assets<WeatherType>,
+    ) -> Option<&'a WeatherType> {
+        let weather_type = weather_types.get(self.weather_at(coordinates)?)?;
+        weather_type_assets.get(weather_type)
+    }
+
+    /// Change the weather of every zone to the weather of its season on `day` of the year.
+    ///
+    /// ## Returns
+    /// The zones whose weather changed.
+    pub fn update(&mut self, day: u16) -> Vec<&WeatherZone> {
+        let mut changed = vec![false; self.zones.len()];
+        for (zone, changed) in self.zones.iter_mut().zip(&mut changed) {
+            let weather = scheduled_weather(&zone.seasons, day).cloned();
+            if zone.weather != weather {
+                zone.weather = weather;
+                *changed = true;
+            }
+        }
+        self.zones
+            .iter()
+            .zip(changed)
+            .filter_map(|(zone, changed)| changed.then_some(zone))
+            .collect()
+    }
+}
+
+/// The movement costs of every tile as defined by its tile type.
+/// Cached, since pathfinding looks them up far more often than tile types change.
+#[derive(Resource, Reflect, Debug, Clone, PartialEq, Eq)]
+pub struct TileMovementCosts(pub HexagonalMap<MovementCosts>);
+
 /// This data structure represents a hexagonal map made up of hexagons.
 /// It allows you to retrieve hex tile entities by their coordinates.
 /// Since this is the heart of this game I wanted to design it in a very performant manner (and because it is fun).
@@ -154,6 +555,17 @@ pub struct HexagonalMap<T> {
 }
 
 impl<T> HexagonalMap<T> {
+    /// Create a map of the given radius by calling `f` with the coordinates of every tile.
+    pub fn from_fn(radius: Radius, mut f: impl FnMut(AxialCoordinates) -> T) -> Self {
+        let amount_tiles = u32::from(radius.into_amount_tiles()) as usize;
+        Self {
+            tiles: (0..amount_tiles)
+                .map(|index| f(index_to_coordinates(radius, index)))
+                .collect(),
+            radius,
+        }
+    }
+
     /// Retrieve a tile by its axial coordinates.
     /// The time complexity of this function is O(1) and it is additionally somewhat optimized to avoid branching and other expensive operations to the best of my abilities.
     ///
@@ -178,6 +590,23 @@ impl<T> HexagonalMap<T> {
         Some(unsafe { self.get_unchecked(coordinates) })
     }
 
+    /// Retrieve a tile mutably by its axial coordinates.
+    ///
+    /// ## Returns
+    /// - Some(&mut T): If the distance of the coordinates from the center of the map is less or equal than its radius ([HexagonalMap::radius()]).
+    /// - None: If the distance of the coordinates from the center of the map is greater than its radius ([HexagonalMap::radius()]).
+    pub fn get_mut(&mut self, coordinates: AxialCoordinates) -> Option<&mut T> {
+        if !self.contains(coordinates) {
+            return None;
+        }
+        let index = coordinates_to_index(self.radius, coordinates);
+        self.tiles.get_mut(index)
+    }
+
+    pub fn contains(&self, coordinates: AxialCoordinates) -> bool {
+        coordinates.distance_to_origin() <= self.radius as u32
+    }
+
     /// Convert a vec to [HexagonalMap].
     ///
     /// ## Returns
@@ -198,6 +627,13 @@ impl<T> HexagonalMap<T> {
             .map(move |(index, tile)| (tile, index_to_coordinates(self.radius, index)))
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = (&T, AxialCoordinates)> {
+        self.tiles
+            .iter()
+            .enumerate()
+            .map(|(index, tile)| (tile, index_to_coordinates(self.radius, index)))
+    }
+
     pub fn into_vec(self) -> Vec<T> {
         self.tiles
     }
@@ -211,6 +647,30 @@ impl<T> HexagonalMap<T> {
     }
 }
 
+impl HexagonalMap<Tile> {
+    /// Retrieve the connection between two adjacent tiles, no matter which of both owns it.
+    ///
+    /// ## Returns
+    /// - None: If the tiles are not adjacent or one of them is not on the map.
+    pub fn connection_between(
+        &self,
+        a: AxialCoordinates,
+        b: AxialCoordinates,
+    ) -> Option<TileConnectionEntity> {
+        if !self.contains(a) || !self.contains(b) {
+            return None;
+        }
+        let connection = |owner: AxialCoordinates, other: AxialCoordinates| {
+            let direction = owner.neighbours()[..3]
+                .iter()
+                .position(|neighbour| *neighbour == other)?;
+            self.get(owner)
+                .map(|tile| tile.tile_connection_entities()[direction])
+        };
+        connection(a, b).or_else(|| connection(b, a))
+    }
+}
+
 impl<T> TryFrom<Vec<T>> for HexagonalMap<T> {
     type Error = InvalidTileAmount;
 
@@ -241,7 +701,13 @@ mod tests_hexagonal_map {
         },
     };
 
-    use super::{radius_and_amount_tiles::Radius, HexagonalMap};
+    use bevy::prelude::Entity;
+
+    use super::{
+        radius_and_amount_tiles::Radius, Calendar, HexagonalMap, Tile, TileControl, Weather,
+        WeatherZone,
+    };
+    use crate::gameplay_plugin::{events::TileCaptured, weather::Season};
 
     // A Vec of radius to coordinates, where each coordinate is at its correct index.
     fn coordinates() -> Vec<(Radius, Vec<AxialCoordinates>)> {
@@ -594,4 +1060,137 @@ mod tests_hexagonal_map {
             }
         }
     }
+
+    #[test]
+    fn test_connection_between() {
+        // Every tile owns the connections with the ids `index * 3`, `index * 3 + 1` and `index * 3 + 2`.
+        let hexagonal_map = HexagonalMap::from_fn(Radius::Radius1, |coordinates| {
+            let index = coordinates_to_index(Radius::Radius1, coordinates) as u32;
+            Tile::new(
+                Entity::from_raw(100 + index),
+                Entity::from_raw(index * 3),
+                Entity::from_raw(index * 3 + 1),
+                Entity::from_raw(index * 3 + 2),
+            )
+        });
+        let center = AxialCoordinates::new(0, 0);
+        let center_index = coordinates_to_index(Radius::Radius1, center) as u32;
+        let [right, lower_right, lower_left, left, upper_left, upper_right] = center.neighbours();
+
+        // Connections owned by the center.
+        for (direction, neighbour) in [right, lower_right, lower_left].into_iter().enumerate() {
+            let connection = Some(Entity::from_raw(center_index * 3 + direction as u32));
+            assert_eq!(
+                hexagonal_map.connection_between(center, neighbour),
+                connection
+            );
+            assert_eq!(
+                hexagonal_map.connection_between(neighbour, center),
+                connection
+            );
+        }
+        // Connections owned by the neighbours.
+        for (direction, neighbour) in [left, upper_left, upper_right].into_iter().enumerate() {
+            let neighbour_index = coordinates_to_index(Radius::Radius1, neighbour) as u32;
+            let connection = Some(Entity::from_raw(neighbour_index * 3 + direction as u32));
+            assert_eq!(
+                hexagonal_map.connection_between(center, neighbour),
+                connection
+            );
+        }
+
+        // Not adjacent.
+        assert_eq!(hexagonal_map.connection_between(left, right), None);
+        assert_eq!(hexagonal_map.connection_between(center, center), None);
+        // Off the map.
+        assert_eq!(
+            hexagonal_map.connection_between(right, AxialCoordinates::new(2, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_capture() {
+        let red = "red".to_string();
+        let blue = "blue".to_string();
+        let coordinates = AxialCoordinates::new(0, 0);
+        let mut tile_control = TileControl(HexagonalMap::from_fn(Radius::Radius1, |_| None));
+
+        assert_eq!(
+            tile_control.capture(coordinates, &red, [&red].into_iter()),
+            Some(TileCaptured {
+                coordinates,
+                previous_controller: None,
+                controller: red.clone(),
+            })
+        );
+        // Already controlled.
+        assert_eq!(
+            tile_control.capture(coordinates, &red, [].into_iter()),
+            None
+        );
+        // Defended by a unit of red.
+        assert_eq!(
+            tile_control.capture(coordinates, &blue, [&blue, &red].into_iter()),
+            None
+        );
+        assert_eq!(tile_control.controller(coordinates), Some(&red));
+        assert_eq!(
+            tile_control
+                .capture(coordinates, &blue, [&blue].into_iter())
+                .map(|tile_captured| tile_captured.previous_controller),
+            Some(Some(red))
+        );
+        assert_eq!(tile_control.controller(coordinates), Some(&blue));
+        // Off the map.
+        assert_eq!(
+            tile_control.capture(AxialCoordinates::new(2, 0), &blue, [].into_iter()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_calendar() {
+        let calendar = Calendar::new(350, 7);
+        assert_eq!(calendar.day_of_year(1), 350);
+        assert_eq!(calendar.day_of_year(2), 357);
+        // The year wraps around.
+        assert_eq!(calendar.day_of_year(3), 364);
+        assert_eq!(calendar.day_of_year(4), 6);
+        assert_eq!(Calendar::new(0, 0).day_of_year(100), 0);
+    }
+
+    #[test]
+    fn test_weather_update() {
+        let zones = vec![
+            WeatherZone::new(
+                "north".to_string(),
+                vec![
+                    Season::new(300, Some("snow".to_string())),
+                    Season::new(90, None),
+                ],
+            ),
+            WeatherZone::new("south".to_string(), vec![]),
+        ];
+        // Only the center tile lies in the north.
+        let tile_zones = HexagonalMap::from_fn(Radius::Radius1, |coordinates| {
+            (coordinates == AxialCoordinates::new(0, 0)).then_some(0)
+        });
+        let mut weather = Weather::new(zones, tile_zones);
+
+        let changed: Vec<_> = weather
+            .update(10)
+            .into_iter()
+            .map(|zone| zone.id().clone())
+            .collect();
+        assert_eq!(changed, vec!["north".to_string()]);
+        assert_eq!(
+            weather.weather_at(AxialCoordinates::new(0, 0)),
+            Some(&"snow".to_string())
+        );
+        assert_eq!(weather.weather_at(AxialCoordinates::new(1, 0)), None);
+        assert!(weather.update(20).is_empty());
+        assert_eq!(weather.update(100).len(), 1);
+        assert_eq!(weather.weather_at(AxialCoordinates::new(0, 0)), None);
+    }
 }
@@ -1,17 +1,96 @@
-use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-use super::components::TempConnectionComponent;
+use bevy::reflect::Reflect;
+use serde::{Deserialize, Serialize};
 
 pub type TileTypePath = String;
+pub type UnitTypePath = String;
+pub type ConnectionTypePath = String;
+pub type FactionId = String;
+pub type ImprovementTypePath = String;
+pub type WeatherTypePath = String;
+pub type WeatherZoneId = String;
+pub type FormationId = String;
+
+/// The size of a formation, from the smallest to the largest.
+#[derive(
+    Reflect, Serialize, Deserialize, Copy, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum Echelon {
+    Division,
+    Corps,
+    Army,
+}
+
+fn first_turn() -> u32 {
+    1
+}
+
+fn is_empty<T>(vec: &[T]) -> bool {
+    vec.is_empty()
+}
+
+fn is_empty_map<K, V>(map: &BTreeMap<K, V>) -> bool {
+    map.is_empty()
+}
+
+fn is_zero(value: &u16) -> bool {
+    *value == 0
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SaveFileImprovement {
+    pub improvement_type: ImprovementTypePath,
+    /// `0` for improvements that are already built.
+    #[serde(default)]
+    pub remaining_build_turns: u8,
+    #[serde(default)]
+    pub lost_durability: u8,
+}
+
+/// See [TerrainWork](super::components::TerrainWork).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SaveFileTerrainWork {
+    pub event: String,
+    pub remaining_turns: u8,
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct SaveFileTileData {
     pub tile_type: TileTypePath,
+    /// See [Elevation](super::components::Elevation).
+    #[serde(default)]
+    pub elevation: u8,
+    /// The faction controlling the tile, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub controller: Option<FactionId>,
+    #[serde(default, skip_serializing_if = "is_empty")]
+    pub improvements: Vec<SaveFileImprovement>,
+    /// Overrides the texture variant picked from the coordinates of the tile and the seed of the map.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub texture_variant: Option<u8>,
+    /// Tiles outside of weather zones never have weather.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weather_zone: Option<WeatherZoneId>,
+    /// See [TerrainDamage](super::components::TerrainDamage).
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub terrain_damage: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terrain_work: Option<SaveFileTerrainWork>,
+    /// The supply reaching the tile for each faction, as traced at the start of the turn.
+    /// The supply of every faction is traced when the game starts if no tile has any.
+    #[serde(default, skip_serializing_if = "is_empty_map")]
+    pub supply: BTreeMap<FactionId, u8>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
 pub struct SaveFileTileConnection {
-    pub temp_data: TempConnectionComponent,
+    /// `None` for a plain border between two tiles.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection_type: Option<ConnectionTypePath>,
+    #[serde(skip_serializing_if = "is_empty")]
+    pub improvements: Vec<SaveFileImprovement>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -22,7 +101,141 @@ pub struct SaveFileTile {
     pub tile_connection_lower_left: SaveFileTileConnection,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SaveFileFaction {
+    pub id: FactionId,
+    /// Defaults to the id.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// The color of the borders of the tiles it controls as sRGB.
+    pub color: (u8, u8, u8),
+    /// The production the faction has stockpiled, see [Production](super::resources::Production).
+    #[serde(default)]
+    pub production: u32,
+}
+
+/// Maps turns to days of the year, see [Calendar](super::resources::Calendar).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct SaveFileCalendar {
+    /// The day of the year of the first turn, starting at 0 for the first of January.
+    pub start_day: u16,
+    pub days_per_turn: u16,
+}
+
+impl Default for SaveFileCalendar {
+    fn default() -> Self {
+        Self {
+            start_day: 0,
+            days_per_turn: 7,
+        }
+    }
+}
+
+/// The state of the [GameRng](super::resources::GameRng).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SaveFileRng {
+    pub seed: u64,
+    /// How many numbers were drawn from each stream so far.
+    #[serde(default)]
+    pub combat_draws: u64,
+    /// Nothing draws from the AI and weather streams yet, so these stay 0.
+    #[serde(default)]
+    pub ai_draws: u64,
+    #[serde(default)]
+    pub weather_draws: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SaveFileSeason {
+    /// The day of the year the season starts at. It lasts until the next season of the zone starts.
+    pub start_day: u16,
+    /// `None` for clear weather.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weather: Option<WeatherTypePath>,
+}
+
+/// An area of tiles sharing the same weather over the year.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SaveFileWeatherZone {
+    pub id: WeatherZoneId,
+    pub seasons: Vec<SaveFileSeason>,
+}
+
+/// A formation of the order of battle.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SaveFileFormation {
+    pub id: FormationId,
+    /// Defaults to the id.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    pub echelon: Echelon,
+    /// The faction the formation belongs to.
+    pub owner: FactionId,
+    /// The formation of a higher echelon this one is subordinate to, which has to be defined before it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<FormationId>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SaveFileUnit {
+    pub unit_type: UnitTypePath,
+    /// The faction the unit belongs to.
+    pub owner: FactionId,
+    /// The axial coordinates `(q, r)` of the tile the unit is on.
+    pub coordinates: (i32, i32),
+    /// The formation the unit belongs to, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub formation: Option<FormationId>,
+    /// The health the unit lost, `0` for a unit at the max health of its unit type.
+    #[serde(default)]
+    pub lost_health: u8,
+    /// The organisation the unit lost, `0` for a unit at the max organisation of its unit type.
+    #[serde(default)]
+    pub lost_organisation: u8,
+    /// The movement points the unit spent this turn.
+    #[serde(default)]
+    pub spent_movement_points: u8,
+    /// How well the unit has dug in, see [Unit](super::components::Unit).
+    #[serde(default)]
+    pub entrenchment: u8,
+    /// The experience the unit gained in combat, which determines its rank.
+    #[serde(default)]
+    pub experience: u16,
+    /// The coordinates of the tiles the unit still moves through, see [MoveOrder](super::components::MoveOrder).
+    #[serde(default, skip_serializing_if = "is_empty")]
+    pub move_order: Vec<(i32, i32)>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct SaveFile {
+    /// The scenario (relative to `assets`) the game was started from.
+    /// Content like tile types is resolved from it, so a game saved elsewhere can still use the content of its scenario.
+    /// `None` for the scenarios themselves.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scenario: Option<String>,
+    /// The current turn, starting at 1.
+    #[serde(default = "first_turn")]
+    pub turn: u32,
+    /// Decides the texture variant of every tile without a `texture_variant`.
+    #[serde(default)]
+    pub seed: u64,
+    /// The source of all other randomness. Scenarios leave it out to get a new seed for every game started from them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rng: Option<SaveFileRng>,
+    #[serde(default)]
+    pub calendar: SaveFileCalendar,
+    #[serde(default, skip_serializing_if = "is_empty")]
+    pub weather_zones: Vec<SaveFileWeatherZone>,
+    #[serde(default)]
+    pub factions: Vec<SaveFileFaction>,
+    /// The faction whose turn it is, see [ActiveFaction](super::resources::ActiveFaction). Defaults to the first faction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_faction: Option<FactionId>,
+    /// The order of battle. Formations are listed before their subordinates.
+    #[serde(default, skip_serializing_if = "is_empty")]
+    pub formations: Vec<SaveFileFormation>,
+    #[serde(default, skip_serializing_if = "is_empty")]
+    pub units: Vec<SaveFileUnit>,
     pub tiles: Vec<SaveFileTile>,
 }
@@ -0,0 +1,188 @@
+//! How supply reaches the units of a faction.
+//!
+//! Supply is sent out by sources like depots and cities and spreads from tile to tile. Every step costs some of it, unless the supply follows a road.
+//! The supply capacity of each tile limits how much of it can flow through the tile, so a forest track carries less than a road.
+//! Units on tiles no supply reaches lose organisation and health each turn.
+
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use super::{
+    combat::Losses,
+    components::AxialCoordinates,
+    resources::{radius_and_amount_tiles::Radius, HexagonalMap},
+};
+
+/// The supply lost with every step that does not follow a road.
+pub const SUPPLY_LOSS_PER_STEP: u8 = 1;
+/// The share of its max organisation a unit loses each turn it is out of supply.
+const OUT_OF_SUPPLY_ORGANISATION_LOSS: f32 = 0.2;
+/// The share of its max health a unit loses each turn it is out of supply.
+const OUT_OF_SUPPLY_HEALTH_LOSS: f32 = 0.1;
+
+/// Calculate how much supply reaches every tile of a map with the given radius.
+/// The supply of a tile is the most any source delivers there, after the losses of each step and limited by the capacity of every tile on the way.
+///
+/// `sources` are the coordinates of each source and the supply it sends out.
+/// `capacity` returns how much supply can flow through a tile, which is `0` for tiles supply can't enter, like those controlled by an enemy.
+/// `loss` returns the supply lost by stepping from the first to the second (adjacent) coordinates or `None` if supply can't cross between them.
+pub fn trace_supply(
+    radius: Radius,
+    sources: impl IntoIterator<Item = (AxialCoordinates, u8)>,
+    mut capacity: impl FnMut(AxialCoordinates) -> u8,
+    mut loss: impl FnMut(AxialCoordinates, AxialCoordinates) -> Option<u8>,
+) -> HexagonalMap<u8> {
+    let mut supply = HexagonalMap::from_fn(radius, |_| 0);
+    let mut frontier: BinaryHeap<(u8, Reverse<(i32, i32)>)> = BinaryHeap::new();
+    for (coordinates, amount) in sources {
+        let amount = amount.min(capacity(coordinates));
+        let Some(best_supply) = supply.get_mut(coordinates) else {
+            continue;
+        };
+        if amount > *best_supply {
+            *best_supply = amount;
+            frontier.push((amount, Reverse((coordinates.q(), coordinates.r()))));
+        }
+    }
+
+    // Like Dijkstra, but the tile with the most supply is spread from first, since supply only shrinks along the way.
+    while let Some((current_supply, Reverse((q, r)))) = frontier.pop() {
+        let current = AxialCoordinates::new(q, r);
+        // More supply has already reached this tile another way.
+        if supply.get(current).copied() > Some(current_supply) {
+            continue;
+        }
+        for neighbour in current.neighbours() {
+            let Some(step_loss) = loss(current, neighbour) else {
+                continue;
+            };
+            let neighbour_supply = current_supply
+                .saturating_sub(step_loss)
+                .min(capacity(neighbour));
+            let Some(best_supply) = supply.get_mut(neighbour) else {
+                continue;
+            };
+            if neighbour_supply > *best_supply {
+                *best_supply = neighbour_supply;
+                frontier.push((neighbour_supply, Reverse((neighbour.q(), neighbour.r()))));
+            }
+        }
+    }
+
+    supply
+}
+
+/// The health and organisation a unit with `max_health` and `max_organisation` loses at the end of a turn on a tile with `attrition_rate`.
+/// Units out of supply additionally lose a share of both.
+pub fn attrition(
+    max_health: u8,
+    max_organisation: u8,
+    attrition_rate: f32,
+    in_supply: bool,
+) -> Losses {
+    let (health_loss, organisation_loss) = if in_supply {
+        (attrition_rate, 0.0)
+    } else {
+        (
+            attrition_rate + OUT_OF_SUPPLY_HEALTH_LOSS,
+            OUT_OF_SUPPLY_ORGANISATION_LOSS,
+        )
+    };
+    let share =
+        |max: u8, loss: f32| (f32::from(max) * loss).ceil().clamp(0.0, f32::from(max)) as u8;
+    Losses {
+        health: share(max_health, health_loss),
+        organisation: share(max_organisation, organisation_loss),
+    }
+}
+
+#[cfg(test)]
+mod tests_supply {
+    use crate::gameplay_plugin::{
+        combat::Losses, components::AxialCoordinates, resources::radius_and_amount_tiles::Radius,
+    };
+
+    use super::{attrition, trace_supply, SUPPLY_LOSS_PER_STEP};
+
+    #[test]
+    fn test_supply_fades_with_distance() {
+        let source = AxialCoordinates::new(0, 0);
+        let supply = trace_supply(
+            Radius::Radius3,
+            [(source, 5)],
+            |_| 4,
+            |_, _| Some(SUPPLY_LOSS_PER_STEP),
+        );
+        // The source is limited by the capacity of its own tile.
+        assert_eq!(supply.get(source), Some(&4));
+        assert_eq!(supply.get(AxialCoordinates::new(1, 0)), Some(&3));
+        assert_eq!(supply.get(AxialCoordinates::new(2, -1)), Some(&2));
+        assert_eq!(supply.get(AxialCoordinates::new(3, -3)), Some(&1));
+    }
+
+    #[test]
+    fn test_roads_and_capacity() {
+        let source = AxialCoordinates::new(-2, 0);
+        let road = [
+            AxialCoordinates::new(-1, 0),
+            AxialCoordinates::new(0, 0),
+            AxialCoordinates::new(1, 0),
+        ];
+        let water = AxialCoordinates::new(-1, 1);
+        let supply = trace_supply(
+            Radius::Radius2,
+            [(source, 3)],
+            |coordinates| match coordinates {
+                coordinates if coordinates == water => 0,
+                coordinates if road.contains(&coordinates) || coordinates == source => 8,
+                _ => 1,
+            },
+            |_, to| {
+                Some(if road.contains(&to) {
+                    0
+                } else {
+                    SUPPLY_LOSS_PER_STEP
+                })
+            },
+        );
+        // Supply follows the road without losing any.
+        assert_eq!(supply.get(AxialCoordinates::new(1, 0)), Some(&3));
+        assert_eq!(supply.get(AxialCoordinates::new(2, 0)), Some(&1));
+        // Supply can't enter water, but tiles behind it are reached around it.
+        assert_eq!(supply.get(water), Some(&0));
+        assert_eq!(supply.get(AxialCoordinates::new(0, 1)), Some(&1));
+    }
+
+    #[test]
+    fn test_blocked_connections() {
+        let source = AxialCoordinates::new(0, 0);
+        let supply = trace_supply(Radius::Radius1, [(source, 4)], |_| 4, |_, _| None);
+        assert_eq!(supply.get(source), Some(&4));
+        assert_eq!(supply.get(AxialCoordinates::new(1, 0)), Some(&0));
+    }
+
+    #[test]
+    fn test_attrition() {
+        assert_eq!(attrition(100, 100, 0.0, true), Losses::default());
+        assert_eq!(
+            attrition(100, 100, 0.05, true),
+            Losses {
+                health: 5,
+                organisation: 0
+            }
+        );
+        assert_eq!(
+            attrition(100, 50, 0.0, false),
+            Losses {
+                health: 10,
+                organisation: 10
+            }
+        );
+        assert_eq!(
+            attrition(5, 5, 1.0, false),
+            Losses {
+                health: 5,
+                organisation: 1
+            }
+        );
+    }
+}
@@ -0,0 +1,28 @@
+//! How units are drawn.
+
+use bevy::{
+    color::{Color, Mix, Srgba},
+    math::{Vec2, Vec3},
+    transform::components::Transform,
+};
+
+use super::{components::AxialCoordinates, resources::Faction};
+
+/// Units are drawn above tiles, their improvements and their weather.
+const Z: f32 = 3.0;
+/// Units are drawn smaller than their tile, so the terrain around them stays visible.
+const SCALE: f32 = 0.6;
+/// How much of the color of their faction units are tinted with, so the texture stays recognizable.
+const FACTION_TINT: f32 = 0.5;
+
+/// Place a unit on the tile at `coordinates`.
+pub fn transform(coordinates: AxialCoordinates) -> Transform {
+    Transform::from_translation(Vec2::from(coordinates).extend(Z)).with_scale(Vec3::splat(SCALE))
+}
+
+/// The tint of units owned by `faction`.
+pub fn tint(faction: &Faction) -> Color {
+    Srgba::WHITE
+        .mix(&faction.color().to_srgba(), FACTION_TINT)
+        .into()
+}
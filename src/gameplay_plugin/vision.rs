@@ -0,0 +1,25 @@
+//! How far units see from the tile they stand on.
+
+use super::{components::Elevation, elevation};
+
+/// The vision range of a unit with `base_range` standing on a tile with the `tile_vision_bonus` of its tile type at `elevation`.
+/// Both the tile type and the height of the terrain extend the range.
+pub fn vision_range(base_range: u8, tile_vision_bonus: u8, elevation: Elevation) -> u8 {
+    base_range
+        .saturating_add(tile_vision_bonus)
+        .saturating_add(elevation::vision_bonus(elevation))
+}
+
+#[cfg(test)]
+mod tests_vision {
+    use super::vision_range;
+    use crate::gameplay_plugin::components::Elevation;
+
+    #[test]
+    fn test_vision_range() {
+        assert_eq!(vision_range(2, 0, Elevation::new(0)), 2);
+        assert_eq!(vision_range(2, 1, Elevation::new(0)), 3);
+        assert_eq!(vision_range(2, 1, Elevation::new(4)), 5);
+        assert_eq!(vision_range(u8::MAX, 1, Elevation::new(4)), u8::MAX);
+    }
+}
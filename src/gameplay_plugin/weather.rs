@@ -0,0 +1,136 @@
+//! How the time of the year and the weather change the properties of tiles.
+
+use bevy::reflect::Reflect;
+
+use super::{
+    assets::{self, MovementCosts},
+    save_file::{TileTypePath, WeatherTypePath},
+};
+
+pub const DAYS_PER_YEAR: u16 = 365;
+const MONTHS: [(&str, u16); 12] = [
+    ("January", 31),
+    ("February", 28),
+    ("March", 31),
+    ("April", 30),
+    ("May", 31),
+    ("June", 30),
+    ("July", 31),
+    ("August", 31),
+    ("September", 30),
+    ("October", 31),
+    ("November", 30),
+    ("December", 31),
+];
+
+/// A part of the year with the same weather in a weather zone.
+#[derive(Reflect, Debug, Clone, PartialEq, Eq)]
+pub struct Season {
+    start_day: u16,
+    weather: Option<WeatherTypePath>,
+}
+
+impl Season {
+    pub fn new(start_day: u16, weather: Option<WeatherTypePath>) -> Self {
+        Self { start_day, weather }
+    }
+
+    /// The day of the year the season starts at. It lasts until the next season starts.
+    pub fn start_day(&self) -> u16 {
+        self.start_day
+    }
+
+    /// `None` for clear weather.
+    pub fn weather(&self) -> Option<&WeatherTypePath> {
+        self.weather.as_ref()
+    }
+}
+
+/// The weather on `day` of the year, which is the one of the last season that started.
+/// Before the first season of the year starts, the last season of the previous year lasts.
+pub fn scheduled_weather(seasons: &[Season], day: u16) -> Option<&WeatherTypePath> {
+    seasons
+        .iter()
+        .filter(|season| season.start_day <= day)
+        .max_by_key(|season| season.start_day)
+        .or_else(|| seasons.iter().max_by_key(|season| season.start_day))
+        .and_then(Season::weather)
+}
+
+/// The day of the month (starting at 1) and the name of the month of `day` of the year.
+pub fn date(day: u16) -> (u16, &'static str) {
+    let mut day = day % DAYS_PER_YEAR;
+    for (month, days) in MONTHS {
+        if day < days {
+            return (day + 1, month);
+        }
+        day -= days;
+    }
+    unreachable!("BUG: The months add up to a year.")
+}
+
+/// The movement costs of a tile of the type `tile_type` with `movement_costs` during `weather_type`.
+pub fn movement_costs(
+    tile_type: &TileTypePath,
+    movement_costs: &MovementCosts,
+    weather_type: Option<&assets::WeatherType>,
+) -> MovementCosts {
+    let Some(weather_type) = weather_type else {
+        return movement_costs.clone();
+    };
+    match weather_type.tile_movement_costs().get(tile_type) {
+        Some(movement_costs) => movement_costs.clone(),
+        None => movement_costs.adjusted(weather_type.movement_cost()),
+    }
+}
+
+/// The combat width of a tile with `combat_width` during `weather_type`.
+/// Weather never reduces it below one.
+pub fn combat_width(combat_width: u8, weather_type: Option<&assets::WeatherType>) -> u8 {
+    let Some(weather_type) = weather_type else {
+        return combat_width;
+    };
+    (f32::from(combat_width) * weather_type.combat_width_modifier())
+        .round()
+        .clamp(1.0, f32::from(u8::MAX)) as u8
+}
+
+#[cfg(test)]
+mod tests_weather {
+    use super::{date, scheduled_weather, Season};
+    use crate::gameplay_plugin::assets::{MovementClass, MovementCosts};
+
+    #[test]
+    fn test_scheduled_weather() {
+        let seasons = [
+            Season::new(60, Some("mud".to_string())),
+            Season::new(100, None),
+            Season::new(330, Some("snow".to_string())),
+        ];
+        assert_eq!(scheduled_weather(&seasons, 60), Some(&"mud".to_string()));
+        assert_eq!(scheduled_weather(&seasons, 99), Some(&"mud".to_string()));
+        assert_eq!(scheduled_weather(&seasons, 200), None);
+        assert_eq!(scheduled_weather(&seasons, 340), Some(&"snow".to_string()));
+        // The winter lasts into the next year.
+        assert_eq!(scheduled_weather(&seasons, 10), Some(&"snow".to_string()));
+        assert_eq!(scheduled_weather(&[], 10), None);
+    }
+
+    #[test]
+    fn test_date() {
+        assert_eq!(date(0), (1, "January"));
+        assert_eq!(date(31), (1, "February"));
+        assert_eq!(date(58), (28, "February"));
+        assert_eq!(date(364), (31, "December"));
+        assert_eq!(date(365), (1, "January"));
+    }
+
+    #[test]
+    fn test_adjusted_movement_costs() {
+        let movement_costs = MovementCosts::default().adjusted(2);
+        assert_eq!(movement_costs.get(MovementClass::Foot), Some(3));
+        // Entering a tile is never free.
+        let movement_costs = movement_costs.adjusted(-5);
+        assert_eq!(movement_costs.get(MovementClass::Wheeled), Some(1));
+    }
+}
@@ -20,11 +20,13 @@ use bevy_egui::EguiPlugin;
 use main_menu_plugin::MainMenuPlugin;
 use resources::SaveFilePath;
 use systems::bevy_inspector_panel;
+use toast_plugin::ToastPlugin;
 
 use crate::gameplay_plugin::GameplayPlugin;
 
 mod gameplay_plugin;
 mod main_menu_plugin;
+mod toast_plugin;
 
 mod cleanup;
 
@@ -50,7 +52,7 @@ impl Plugin for MyGamePlugin {
         debug_assert!(app.is_plugin_added::<TransformPlugin>());
         debug_assert!(app.is_plugin_added::<TimePlugin>());
 
-        app.add_plugins((MainMenuPlugin, GameplayPlugin));
+        app.add_plugins((MainMenuPlugin, GameplayPlugin, ToastPlugin));
 
         app.register_type::<SaveFilePath>()
             .register_type_data::<SaveFilePath, ReflectResource>();
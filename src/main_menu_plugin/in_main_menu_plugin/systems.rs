@@ -34,21 +34,28 @@ pub fn temporary_main_menu(world: &mut World) {
         .resizable(true)
         .show(ctx.clone().get_mut(), |ui| {
             ScrollArea::both().show(ui, |ui| {
-                for entry in fs::read_dir("assets/save_files/scenarios").unwrap() {
-                    let path = entry.unwrap().path();
-                    if path.is_dir() {
-                        let dir_name = path.file_name().unwrap().to_string_lossy();
-                        if ui.button(dir_name.clone()).clicked() {
-                            world.insert_resource(SaveFilePath::new(format!(
-                                "save_files/scenarios/{dir_name}"
-                            )));
-                            world
-                                .get_resource_mut::<NextState<GameStates>>()
-                                .unwrap()
-                                .as_mut()
-                                .set(GameStates::Gameplay);
-                            return;
-                        };
+                for directory in ["scenarios", "saves"] {
+                    // There are no saves until the game was saved for the first time.
+                    let Ok(entries) = fs::read_dir(format!("assets/save_files/{directory}")) else {
+                        continue;
+                    };
+                    ui.heading(directory);
+                    for entry in entries {
+                        let path = entry.unwrap().path();
+                        if path.is_dir() {
+                            let dir_name = path.file_name().unwrap().to_string_lossy();
+                            if ui.button(dir_name.clone()).clicked() {
+                                world.insert_resource(SaveFilePath::new(format!(
+                                    "save_files/{directory}/{dir_name}"
+                                )));
+                                world
+                                    .get_resource_mut::<NextState<GameStates>>()
+                                    .unwrap()
+                                    .as_mut()
+                                    .set(GameStates::Gameplay);
+                                return;
+                            };
+                        }
                     }
                 }
                 ui.allocate_space(ui.available_size());
@@ -0,0 +1,23 @@
+use bevy::{
+    app::{Plugin, Update},
+    prelude::ReflectResource,
+};
+use systems::show_toasts;
+
+pub use resources::Toasts;
+
+mod resources;
+mod systems;
+
+/// Shows short notifications in the corner of the screen, no matter the state of the game.
+pub struct ToastPlugin;
+
+impl Plugin for ToastPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.register_type::<Toasts>()
+            .register_type_data::<Toasts, ReflectResource>()
+            .init_resource::<Toasts>();
+
+        app.add_systems(Update, show_toasts);
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    prelude::Resource,
+    reflect::Reflect,
+    time::{Timer, TimerMode},
+};
+
+const TOAST_DURATION_SECS: f32 = 4.0;
+/// Older toasts are dropped once there are more than this, so a burst of notifications doesn't cover the screen.
+const MAX_TOASTS: usize = 5;
+
+#[derive(Reflect, Copy, Debug, Clone, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Error,
+}
+
+#[derive(Reflect, Debug, Clone)]
+pub struct Toast {
+    kind: ToastKind,
+    message: String,
+    timer: Timer,
+}
+
+impl Toast {
+    pub fn kind(&self) -> ToastKind {
+        self.kind
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub(super) fn timer(&self) -> &Timer {
+        &self.timer
+    }
+
+    pub(super) fn timer_mut(&mut self) -> &mut Timer {
+        &mut self.timer
+    }
+}
+
+/// The toasts currently shown, oldest first.
+#[derive(Resource, Reflect, Default, Debug, Clone)]
+pub struct Toasts(pub(super) VecDeque<Toast>);
+
+impl Toasts {
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push(ToastKind::Info, message.into());
+    }
+
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.push(ToastKind::Error, message.into());
+    }
+
+    fn push(&mut self, kind: ToastKind, message: String) {
+        if self.0.len() == MAX_TOASTS {
+            self.0.pop_front();
+        }
+        self.0.push_back(Toast {
+            kind,
+            message,
+            timer: Timer::from_seconds(TOAST_DURATION_SECS, TimerMode::Once),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests_toasts {
+    use super::{ToastKind, Toasts, MAX_TOASTS};
+
+    #[test]
+    fn test_push_drops_oldest() {
+        let mut toasts = Toasts::default();
+        for index in 0..=MAX_TOASTS {
+            toasts.info(index.to_string());
+        }
+        toasts.error("error");
+        assert_eq!(toasts.0.len(), MAX_TOASTS);
+        assert_eq!(toasts.0.front().unwrap().message(), "2");
+        assert_eq!(toasts.0.back().unwrap().kind(), ToastKind::Error);
+    }
+}
@@ -0,0 +1,41 @@
+use bevy::{
+    prelude::{Query, Res, ResMut, With},
+    time::Time,
+};
+use bevy_egui::{
+    egui::{Align2, Area, Color32, Frame, Id, RichText},
+    EguiContext,
+};
+use bevy_window::PrimaryWindow;
+
+use super::resources::{ToastKind, Toasts};
+
+pub(super) fn show_toasts(
+    mut egui_context: Query<&mut EguiContext, With<PrimaryWindow>>,
+    mut toasts: ResMut<Toasts>,
+    time: Res<Time>,
+) {
+    for toast in &mut toasts.0 {
+        toast.timer_mut().tick(time.delta());
+    }
+    toasts.0.retain(|toast| !toast.timer().finished());
+    if toasts.0.is_empty() {
+        return;
+    }
+    let Ok(mut egui_context) = egui_context.get_single_mut() else {
+        return;
+    };
+    Area::new(Id::new("toasts"))
+        .anchor(Align2::RIGHT_BOTTOM, [-8.0, -8.0])
+        .show(egui_context.get_mut(), |ui| {
+            for toast in &toasts.0 {
+                let color = match toast.kind() {
+                    ToastKind::Info => Color32::WHITE,
+                    ToastKind::Error => Color32::LIGHT_RED,
+                };
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.label(RichText::new(toast.message()).color(color));
+                });
+            }
+        });
+}